@@ -0,0 +1,33 @@
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use vec_btree_map::{BufferedVecBTreeMap, VecBTreeMap};
+
+fn insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("random insert");
+    group.sample_size(10);
+    for len in [1_000u64, 10_000, 100_000] {
+        let keys = move || (0..len).map(move |i| i.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+
+        group.bench_function(BenchmarkId::new("VecBTreeMap", len), |b| {
+            b.iter(|| {
+                let mut map = VecBTreeMap::new();
+                for k in keys() {
+                    map.insert(k, k);
+                }
+                black_box(map)
+            })
+        });
+        group.bench_function(BenchmarkId::new("BufferedVecBTreeMap", len), |b| {
+            b.iter(|| {
+                let mut map = BufferedVecBTreeMap::new();
+                for k in keys() {
+                    map.insert(k, k);
+                }
+                black_box(map.into_map())
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, insert);
+criterion_main!(benches);
@@ -0,0 +1,29 @@
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use vec_btree_map::{FrozenVecBTreeMap, VecBTreeMap};
+
+fn lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup");
+    for len in [1_000u64, 100_000, 1_000_000] {
+        let map: VecBTreeMap<u64, u64> = (0..len).map(|i| (i * 2, i)).collect();
+        let frozen: FrozenVecBTreeMap<u64, u64> = map.clone().freeze();
+
+        group.bench_function(BenchmarkId::new("VecBTreeMap", len), |b| {
+            let mut k = 0;
+            b.iter(|| {
+                k = (k + 7919) % (len * 2);
+                black_box(map.get(&k));
+            })
+        });
+        group.bench_function(BenchmarkId::new("FrozenVecBTreeMap", len), |b| {
+            let mut k = 0;
+            b.iter(|| {
+                k = (k + 7919) % (len * 2);
+                black_box(frozen.get(&k));
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, lookup);
+criterion_main!(benches);
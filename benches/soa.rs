@@ -0,0 +1,29 @@
+use criterion::{BenchmarkId, Criterion, black_box, criterion_group, criterion_main};
+use vec_btree_map::{SoaVecBTreeMap, VecBTreeMap};
+
+const LEN: u64 = 10_000;
+
+fn lookup<const N: usize>(c: &mut Criterion) {
+    let mut group = c.benchmark_group("lookup");
+    let aos: VecBTreeMap<u64, [u8; N]> = (0..LEN).map(|i| (i * 2, [0; N])).collect();
+    let soa: SoaVecBTreeMap<u64, [u8; N]> = (0..LEN).map(|i| (i * 2, [0; N])).collect();
+
+    group.bench_function(BenchmarkId::new("VecBTreeMap", N), |b| {
+        let mut k = 0;
+        b.iter(|| {
+            k = (k + 7919) % (LEN * 2);
+            black_box(aos.get(&k));
+        })
+    });
+    group.bench_function(BenchmarkId::new("SoaVecBTreeMap", N), |b| {
+        let mut k = 0;
+        b.iter(|| {
+            k = (k + 7919) % (LEN * 2);
+            black_box(soa.get(&k));
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, lookup::<8>, lookup::<256>, lookup::<1024>);
+criterion_main!(benches);
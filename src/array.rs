@@ -0,0 +1,560 @@
+use crate::{
+    CapacityError, Comparable, Comparator, Iter, IterMut, Keys, Natural, Range, RangeMut, Values,
+    ValuesMut, equal_range, range_indices,
+};
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator, Iterator};
+use core::mem::{self, MaybeUninit};
+use core::ops::{self, Deref, Index, RangeBounds};
+use core::{ptr, slice};
+
+/// A map with a fixed capacity of `N` key-value pairs, stored inline without any heap
+/// allocation.
+///
+/// It offers the lookup, insertion and iteration API of [`VecBTreeMap`](crate::VecBTreeMap),
+/// but inserting into a full map fails with a [`CapacityError`] holding the rejected pair.
+///
+/// # Examples
+///
+/// ```
+/// use vec_btree_map::ArrayVecBTreeMap;
+///
+/// let mut map = ArrayVecBTreeMap::<_, _, 2>::new();
+///
+/// assert_eq!(map.insert("b", 2), Ok(None));
+/// assert_eq!(map.insert("a", 1), Ok(None));
+/// assert_eq!(map.insert("a", 3), Ok(Some(1)));
+/// assert_eq!(map.insert("c", 4).unwrap_err().into_inner(), ("c", 4));
+///
+/// assert_eq!(map.get("a"), Some(&3));
+/// assert_eq!(*map, [("a", 3), ("b", 2)]);
+/// ```
+pub struct ArrayVecBTreeMap<K, V, const N: usize, C = Natural> {
+    base: [MaybeUninit<(K, V)>; N],
+    len: usize,
+    cmp: C,
+}
+
+impl<K, V, const N: usize, C: Default> Default for ArrayVecBTreeMap<K, V, N, C> {
+    #[inline]
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<K, V, const N: usize> ArrayVecBTreeMap<K, V, N> {
+    /// Constructs a new, empty `ArrayVecBTreeMap<K, V, N>`.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_comparator(Natural)
+    }
+}
+
+impl<K, V, const N: usize, C> ArrayVecBTreeMap<K, V, N, C> {
+    /// Constructs a new, empty `ArrayVecBTreeMap<K, V, N, C>` which orders its keys with `cmp`.
+    #[inline]
+    #[must_use]
+    pub const fn with_comparator(cmp: C) -> Self {
+        Self {
+            base: [const { MaybeUninit::uninit() }; N],
+            len: 0,
+            cmp,
+        }
+    }
+
+    /// Returns a reference to the map's [`Comparator`].
+    #[inline]
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /// Returns the number of key-value pairs the map can hold, which is always `N`.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the map holds `N` key-value pairs.
+    #[inline]
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    /// Returns the key-value pairs as a slice, sorted by key.
+    #[inline]
+    pub fn as_slice(&self) -> &[(K, V)] {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { slice::from_raw_parts(self.base.as_ptr().cast(), self.len) }
+    }
+
+    /// Returns the key-value pairs as a mutable slice, sorted by key.
+    ///
+    /// Only the values may be changed, the order of the keys must be kept.
+    #[inline]
+    pub(crate) fn as_mut_slice(&mut self) -> &mut [(K, V)] {
+        // SAFETY: the first `len` elements are initialized.
+        unsafe { slice::from_raw_parts_mut(self.base.as_mut_ptr().cast(), self.len) }
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.as_slice().iter())
+    }
+
+    /// Gets a mutable iterator over the entries of the map, sorted by key.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(self.as_mut_slice().iter_mut())
+    }
+
+    /// Gets an iterator over the keys of the map, in sorted order.
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(self.as_slice().iter())
+    }
+
+    /// Gets an iterator over the values of the map, in order by key.
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values::new(self.as_slice().iter())
+    }
+
+    /// Gets a mutable iterator over the values of the map, in order by key.
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(self.as_mut_slice().iter_mut())
+    }
+
+    /// Returns the key-value pair at the given position, or [`None`] if
+    /// `index` is out of bounds.
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.as_slice().get(index).map(|e| (&e.0, &e.1))
+    }
+
+    /// Returns the key and a mutable reference to the value at the given position,
+    /// or [`None`] if `index` is out of bounds.
+    #[inline]
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        self.as_mut_slice().get_mut(index).map(|e| (&e.0, &mut e.1))
+    }
+
+    /// Inserts `e` at position `i` without checking the capacity or the order.
+    ///
+    /// # Safety
+    ///
+    /// `i <= len < N` must hold.
+    #[inline]
+    pub(crate) unsafe fn insert_at(&mut self, i: usize, e: (K, V)) {
+        let p = self.base.as_mut_ptr().cast::<(K, V)>();
+        // SAFETY: the caller guarantees there is room for one more element after `len`.
+        unsafe {
+            ptr::copy(p.add(i), p.add(i + 1), self.len - i);
+            p.add(i).write(e);
+        }
+        self.len += 1;
+    }
+
+    /// Removes and returns the key-value pair at position `i`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    #[inline]
+    pub(crate) fn remove_at(&mut self, i: usize) -> (K, V) {
+        assert!(i < self.len, "index out of bounds");
+        self.len -= 1;
+        let p = self.base.as_mut_ptr().cast::<(K, V)>();
+        // SAFETY: `i` was initialized, and the elements after it are moved down by one.
+        unsafe {
+            let e = p.add(i).read();
+            ptr::copy(p.add(i + 1), p.add(i), self.len - i);
+            e
+        }
+    }
+
+    /// Removes the last key-value pair from the map and returns it, or [`None`] if it
+    /// is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.len = self.len.checked_sub(1)?;
+        // SAFETY: the element at the old last position is initialized and no longer tracked.
+        Some(unsafe { self.base[self.len].assume_init_read() })
+    }
+
+    /// Clears the map, removing all key-value pairs.
+    #[inline]
+    pub fn clear(&mut self) {
+        let pairs: *mut [(K, V)] = self.as_mut_slice();
+        self.len = 0;
+        // SAFETY: the pairs were initialized and are no longer tracked.
+        unsafe { ptr::drop_in_place(pairs) }
+    }
+}
+
+impl<K, V, const N: usize, C> ArrayVecBTreeMap<K, V, N, C>
+where
+    C: Comparator<K>,
+{
+    /// Binary searches this map for a given key.
+    ///
+    /// If the key is found then [`Result::Ok`] is returned, containing the
+    /// index of the matching key.
+    /// If the key is not found then [`Result::Err`] is returned, containing
+    /// the index where a matching key-value pair could be inserted while maintaining
+    /// sorted order.
+    #[inline]
+    pub fn binary_search<Q>(&self, k: &Q) -> Result<usize, usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.as_slice()
+            .binary_search_by(|e| k.compare(&e.0, &self.cmp).reverse())
+    }
+
+    /// Returns the positions of all keys that compare equal to `k`.
+    #[inline]
+    fn equal_range<Q>(&self, k: &Q) -> ops::Range<usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        equal_range(self.as_slice(), |e| k.compare(&e.0, &self.cmp).reverse())
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.get_key_value(k).map(|(_, v)| v)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let i = self.binary_search(k).ok()?;
+        Some(&mut self.as_mut_slice()[i].1)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let e = &self.as_slice()[self.binary_search(k).ok()?];
+        Some((&e.0, &e.1))
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.binary_search(k).is_ok()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, `Ok(None)` is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and `Ok(Some(old))` is
+    /// returned. The key is not updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns a [`CapacityError`] with the key-value pair if the key is not present
+    /// and the map is full.
+    #[inline]
+    pub fn insert(&mut self, k: K, v: V) -> Result<Option<V>, CapacityError<K, V>> {
+        match self.binary_search(&k) {
+            Ok(i) => Ok(Some(mem::replace(&mut self.as_mut_slice()[i].1, v))),
+            Err(_) if self.is_full() => Err(CapacityError::new((k, v))),
+            Err(i) => {
+                // SAFETY: `i <= len` comes from the binary search and `len < N` was checked.
+                unsafe { self.insert_at(i, (k, v)) };
+                Ok(None)
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.remove_entry(k).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn remove_entry<Q>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let i = self.binary_search(k).ok()?;
+        Some(self.remove_at(i))
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of key-value pairs in the map.
+    ///
+    /// See [`VecBTreeMap::range`](crate::VecBTreeMap::range) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end` and the map contains a key within `end..=start`.
+    /// Other inverted ranges are empty.
+    #[inline]
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let indices = range_indices(&range, self.len, |k| self.equal_range(k));
+        Range::new(self.as_slice()[indices].iter())
+    }
+
+    /// Constructs a mutable double-ended iterator over a sub-range of key-value pairs in the map.
+    ///
+    /// See [`VecBTreeMap::range_mut`](crate::VecBTreeMap::range_mut) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end` and the map contains a key within `end..=start`.
+    /// Other inverted ranges are empty.
+    #[inline]
+    pub fn range_mut<Q, R>(&mut self, range: R) -> RangeMut<'_, K, V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let indices = range_indices(&range, self.len, |k| self.equal_range(k));
+        RangeMut::new(self.as_mut_slice()[indices].iter_mut())
+    }
+}
+
+impl<K, V, const N: usize, C> Drop for ArrayVecBTreeMap<K, V, N, C> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K, V, const N: usize, C> Deref for ArrayVecBTreeMap<K, V, N, C> {
+    type Target = [(K, V)];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<K, V, const N: usize, C, Q> Index<&Q> for ArrayVecBTreeMap<K, V, N, C>
+where
+    C: Comparator<K>,
+    Q: Comparable<K, C> + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// Use [`get_index`](ArrayVecBTreeMap::get_index) to look up a pair by its position instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    #[inline]
+    fn index(&self, k: &Q) -> &Self::Output {
+        self.get(k).expect("no entry found for key")
+    }
+}
+
+impl<K: Clone, V: Clone, const N: usize, C: Clone> Clone for ArrayVecBTreeMap<K, V, N, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let mut map = Self::with_comparator(self.cmp.clone());
+        for (i, e) in self.iter().enumerate() {
+            map.base[i].write((e.0.clone(), e.1.clone()));
+            map.len += 1;
+        }
+        map
+    }
+}
+
+impl<K: PartialEq, V: PartialEq, const N: usize, C> PartialEq for ArrayVecBTreeMap<K, V, N, C> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<K: Eq, V: Eq, const N: usize, C> Eq for ArrayVecBTreeMap<K, V, N, C> {}
+
+impl<K: Hash, V: Hash, const N: usize, C> Hash for ArrayVecBTreeMap<K, V, N, C> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state);
+    }
+}
+
+impl<K: Debug, V: Debug, const N: usize, C> Debug for ArrayVecBTreeMap<K, V, N, C> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, const N: usize, C> IntoIterator for ArrayVecBTreeMap<K, V, N, C> {
+    type Item = (K, V);
+    type IntoIter = ArrayIntoIter<K, V, N>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        let mut map = mem::ManuallyDrop::new(self);
+        // SAFETY: `map` is never used or dropped again, so its comparator has
+        // to be dropped here while the pairs move into the iterator.
+        unsafe { ptr::drop_in_place(&mut map.cmp) };
+        ArrayIntoIter {
+            start: 0,
+            end: map.len,
+            // SAFETY: see above.
+            base: unsafe { ptr::read(&map.base) },
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize, C> IntoIterator for &'a ArrayVecBTreeMap<K, V, N, C> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize, C> IntoIterator for &'a mut ArrayVecBTreeMap<K, V, N, C> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// An owning iterator over the entries of an [`ArrayVecBTreeMap`], sorted by key.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ArrayIntoIter<K, V, const N: usize> {
+    base: [MaybeUninit<(K, V)>; N],
+    start: usize,
+    end: usize,
+}
+
+impl<K, V, const N: usize> ArrayIntoIter<K, V, N> {
+    /// Returns the remaining key-value pairs as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[(K, V)] {
+        // SAFETY: the elements in `start..end` are initialized.
+        unsafe {
+            slice::from_raw_parts(
+                self.base.as_ptr().add(self.start).cast(),
+                self.end - self.start,
+            )
+        }
+    }
+}
+
+impl<K, V, const N: usize> Drop for ArrayIntoIter<K, V, N> {
+    #[inline]
+    fn drop(&mut self) {
+        let len = self.end - self.start;
+        // SAFETY: the remaining pairs were initialized and are never read again.
+        unsafe {
+            let p = self.base.as_mut_ptr().add(self.start).cast::<(K, V)>();
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(p, len));
+        }
+    }
+}
+
+impl<K: Debug, V: Debug, const N: usize> Debug for ArrayIntoIter<K, V, N> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.as_slice()).finish()
+    }
+}
+
+impl<K, V, const N: usize> Iterator for ArrayIntoIter<K, V, N> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.start += 1;
+        // SAFETY: the element was initialized and is no longer tracked.
+        Some(unsafe { self.base[self.start - 1].assume_init_read() })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, const N: usize> DoubleEndedIterator for ArrayIntoIter<K, V, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start == self.end {
+            return None;
+        }
+        self.end -= 1;
+        // SAFETY: the element was initialized and is no longer tracked.
+        Some(unsafe { self.base[self.end].assume_init_read() })
+    }
+}
+
+impl<K, V, const N: usize> ExactSizeIterator for ArrayIntoIter<K, V, N> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+impl<K, V, const N: usize> FusedIterator for ArrayIntoIter<K, V, N> {}
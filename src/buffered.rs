@@ -0,0 +1,505 @@
+use crate::extend::merge;
+use crate::{Comparable, Comparator, IntoIter, Natural, VecBTreeMap, equal_range, range_indices};
+use allocator_api2::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::RangeBounds;
+
+/// The tail is merged into the main run once it holds more than this many pairs,
+/// or more than the square root of the main run's length, whichever is larger.
+const MIN_TAIL: usize = 32;
+
+/// Where a key was found in a [`BufferedVecBTreeMap`].
+enum Slot {
+    /// At this position of the tail.
+    Tail(usize),
+    /// At this position of the main run.
+    Base(usize),
+    /// Nowhere. A new pair belongs at this position of the tail.
+    Vacant(usize),
+}
+
+/// A [`VecBTreeMap`] that buffers new keys in a small sorted tail.
+///
+/// Inserting a new key into a [`VecBTreeMap`] shifts every greater key, which makes bulk
+/// random inserts `O(n)` each. This map instead inserts new keys into a separate tail of
+/// at most about `√n` pairs, and merges the tail into the main run in `O(n)` once it grows
+/// past that, so inserts take amortized `O(√n)` time. Lookups search both runs.
+///
+/// Updating the value of a key that is already present, in either run, never moves
+/// any pairs.
+///
+/// # Examples
+///
+/// ```
+/// use vec_btree_map::{BufferedVecBTreeMap, VecBTreeMap};
+///
+/// let mut map = BufferedVecBTreeMap::new();
+/// for i in (0..100).rev() {
+///     map.insert(i, i * 2);
+/// }
+/// assert_eq!(map.get(&42), Some(&84));
+/// assert!(map.iter().map(|(k, _)| *k).eq(0..100));
+///
+/// let map: VecBTreeMap<_, _> = map.into_map();
+/// assert_eq!(map.len(), 100);
+/// ```
+pub struct BufferedVecBTreeMap<K, V, C = Natural> {
+    base: VecBTreeMap<K, V, C>,
+    /// Sorted, and never shares a key with `base`.
+    tail: Vec<(K, V)>,
+}
+
+impl<K, V, C: Default> Default for BufferedVecBTreeMap<K, V, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<K, V> BufferedVecBTreeMap<K, V> {
+    /// Constructs a new, empty `BufferedVecBTreeMap<K, V>`.
+    ///
+    /// The map will not allocate until it is first inserted into.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_comparator(Natural)
+    }
+}
+
+impl<K, V, C> BufferedVecBTreeMap<K, V, C> {
+    /// Constructs a new, empty `BufferedVecBTreeMap<K, V, C>` which orders its keys with `cmp`.
+    #[inline]
+    #[must_use]
+    pub const fn with_comparator(cmp: C) -> Self {
+        Self {
+            base: VecBTreeMap::with_comparator(cmp),
+            tail: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the map's [`Comparator`].
+    #[inline]
+    pub fn comparator(&self) -> &C {
+        self.base.comparator()
+    }
+
+    /// Returns the number of key-value pairs in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.base.len() + self.tail.len()
+    }
+
+    /// Returns `true` if the map contains no key-value pairs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.base.is_empty() && self.tail.is_empty()
+    }
+
+    /// Returns the number of key-value pairs that are waiting to be merged into the main run.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.tail.len()
+    }
+
+    /// Clears the map, removing all key-value pairs.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.base.base.clear();
+        self.tail.clear();
+    }
+}
+
+impl<K, V, C> BufferedVecBTreeMap<K, V, C>
+where
+    C: Comparator<K>,
+{
+    /// Searches the tail for the given key.
+    #[inline]
+    fn search_tail<Q>(&self, k: &Q) -> Result<usize, usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let cmp = self.base.comparator();
+        self.tail
+            .binary_search_by(|e| k.compare(&e.0, cmp).reverse())
+    }
+
+    /// Finds the given key, searching the tail first and then the main run.
+    ///
+    /// A key lives in at most one of the two runs, so the order only matters for speed,
+    /// and the tail is the shorter one.
+    #[inline]
+    fn find<Q>(&self, k: &Q) -> Slot
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        match self.search_tail(k) {
+            Ok(i) => {
+                debug_assert!(
+                    self.base.binary_search(k).is_err(),
+                    "key is in both the main run and the tail"
+                );
+                Slot::Tail(i)
+            }
+            Err(i) => self
+                .base
+                .binary_search(k)
+                .map_or(Slot::Vacant(i), Slot::Base),
+        }
+    }
+
+    /// Merges the tail into the main run.
+    ///
+    /// This takes `O(n)` time and is done automatically once the tail grows past
+    /// about `√n` pairs. Calling it explicitly makes subsequent lookups search a
+    /// single run again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::BufferedVecBTreeMap;
+    ///
+    /// let mut map = BufferedVecBTreeMap::new();
+    /// map.insert("b", 2);
+    /// map.insert("a", 1);
+    /// assert_eq!(map.pending(), 2);
+    ///
+    /// map.compact();
+    /// assert_eq!(map.pending(), 0);
+    /// assert_eq!(map.len(), 2);
+    /// ```
+    #[inline]
+    pub fn compact(&mut self) {
+        let VecBTreeMap { base, cmp } = &mut self.base;
+        merge(base, mem::take(&mut self.tail), cmp);
+    }
+
+    /// Merges the tail into the main run and returns it as a [`VecBTreeMap`].
+    #[inline]
+    #[must_use]
+    pub fn into_map(mut self) -> VecBTreeMap<K, V, C> {
+        self.compact();
+        self.base
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.get_key_value(k).map(|(_, v)| v)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        match self.find(k) {
+            Slot::Tail(i) => Some(&mut self.tail[i].1),
+            Slot::Base(i) => Some(&mut self.base.base[i].1),
+            Slot::Vacant(_) => None,
+        }
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let (k, v) = match self.find(k) {
+            Slot::Tail(i) => &self.tail[i],
+            Slot::Base(i) => &self.base.base[i],
+            Slot::Vacant(_) => return None,
+        };
+        Some((k, v))
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.get_key_value(k).is_some()
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, [`None`] is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned. The key is not updated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::BufferedVecBTreeMap;
+    ///
+    /// let mut map = BufferedVecBTreeMap::new();
+    /// assert_eq!(map.insert("a", 1), None);
+    /// assert_eq!(map.insert("a", 2), Some(1));
+    /// assert_eq!(map.get("a"), Some(&2));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        match self.find(&k) {
+            Slot::Tail(i) => Some(mem::replace(&mut self.tail[i].1, v)),
+            Slot::Base(i) => Some(mem::replace(&mut self.base.base[i].1, v)),
+            Slot::Vacant(i) => {
+                self.tail.insert(i, (k, v));
+                if self.tail.len() > self.base.len().isqrt().max(MIN_TAIL) {
+                    self.compact();
+                }
+                None
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// Removing a key that has already been merged into the main run takes `O(n)` time.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.remove_entry(k).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn remove_entry<Q>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        match self.find(k) {
+            Slot::Tail(i) => Some(self.tail.remove(i)),
+            Slot::Base(i) => Some(self.base.base.remove(i)),
+            Slot::Vacant(_) => None,
+        }
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    ///
+    /// The main run and the tail are merged lazily.
+    #[inline]
+    pub fn iter(&self) -> BufferedIter<'_, K, V, C> {
+        BufferedIter::new(&self.base.base, &self.tail, &self.base.cmp)
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of key-value pairs in the map.
+    ///
+    /// See [`VecBTreeMap::range`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end` and the map contains a key within `end..=start`.
+    /// Other inverted ranges are empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::BufferedVecBTreeMap;
+    ///
+    /// let mut map: BufferedVecBTreeMap<_, _> = (0..10).map(|i| (i * 2, i)).collect();
+    /// map.insert(5, 50);
+    /// assert!(map.range(3..=8).map(|(k, _)| *k).eq([4, 5, 6, 8]));
+    /// assert_eq!(map.range(3..=8).next_back(), Some((&8, &4)));
+    /// ```
+    #[inline]
+    pub fn range<Q, R>(&self, range: R) -> BufferedIter<'_, K, V, C>
+    where
+        Q: Comparable<K, C> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let cmp = self.base.comparator();
+        let base = range_indices(&range, self.base.len(), |k| self.base.equal_range(k));
+        let tail = range_indices(&range, self.tail.len(), |k| {
+            equal_range(&self.tail, |e| k.compare(&e.0, cmp).reverse())
+        });
+        BufferedIter::new(&self.base.base[base], &self.tail[tail], &self.base.cmp)
+    }
+}
+
+impl<K: Clone, V: Clone, C: Clone> Clone for BufferedVecBTreeMap<K, V, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            tail: self.tail.clone(),
+        }
+    }
+}
+
+impl<K: Debug, V: Debug, C: Comparator<K>> Debug for BufferedVecBTreeMap<K, V, C> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, C: Comparator<K> + Default> FromIterator<(K, V)> for BufferedVecBTreeMap<K, V, C> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        VecBTreeMap::from_iter(iter).into()
+    }
+}
+
+impl<K, V, C: Comparator<K>> Extend<(K, V)> for BufferedVecBTreeMap<K, V, C> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.compact();
+        self.base.extend(iter);
+    }
+}
+
+impl<K, V, C> From<VecBTreeMap<K, V, C>> for BufferedVecBTreeMap<K, V, C> {
+    #[inline]
+    fn from(base: VecBTreeMap<K, V, C>) -> Self {
+        Self {
+            base,
+            tail: Vec::new(),
+        }
+    }
+}
+
+impl<K, V, C: Comparator<K>> From<BufferedVecBTreeMap<K, V, C>> for VecBTreeMap<K, V, C> {
+    #[inline]
+    fn from(map: BufferedVecBTreeMap<K, V, C>) -> Self {
+        map.into_map()
+    }
+}
+
+impl<K, V, C: Comparator<K>> IntoIterator for BufferedVecBTreeMap<K, V, C> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_map().into_iter()
+    }
+}
+
+impl<'a, K, V, C: Comparator<K>> IntoIterator for &'a BufferedVecBTreeMap<K, V, C> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = BufferedIter<'a, K, V, C>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a [`BufferedVecBTreeMap`], sorted by key.
+///
+/// This `struct` is created by the [`iter`](BufferedVecBTreeMap::iter) and
+/// [`range`](BufferedVecBTreeMap::range) methods. It merges the main run and the tail
+/// from both ends.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct BufferedIter<'a, K, V, C = Natural> {
+    base: &'a [(K, V)],
+    tail: &'a [(K, V)],
+    cmp: &'a C,
+}
+
+impl<'a, K, V, C> BufferedIter<'a, K, V, C> {
+    #[inline]
+    const fn new(base: &'a [(K, V)], tail: &'a [(K, V)], cmp: &'a C) -> Self {
+        Self { base, tail, cmp }
+    }
+}
+
+impl<K, V, C> Clone for BufferedIter<'_, K, V, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.base, self.tail, self.cmp)
+    }
+}
+
+impl<K: Debug, V: Debug, C: Comparator<K>> Debug for BufferedIter<'_, K, V, C> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V, C: Comparator<K>> Iterator for BufferedIter<'a, K, V, C> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        // The runs never share a key, so the smaller first key is always taken alone.
+        let run = match (self.base.first(), self.tail.first()) {
+            (Some(b), Some(t)) if self.cmp.compare(&t.0, &b.0).is_lt() => &mut self.tail,
+            (Some(_), _) => &mut self.base,
+            (None, _) => &mut self.tail,
+        };
+        let (e, rest) = run.split_first()?;
+        *run = rest;
+        Some((&e.0, &e.1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, C: Comparator<K>> DoubleEndedIterator for BufferedIter<'_, K, V, C> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let run = match (self.base.last(), self.tail.last()) {
+            (Some(b), Some(t)) if self.cmp.compare(&t.0, &b.0).is_gt() => &mut self.tail,
+            (Some(_), _) => &mut self.base,
+            (None, _) => &mut self.tail,
+        };
+        let (e, rest) = run.split_last()?;
+        *run = rest;
+        Some((&e.0, &e.1))
+    }
+}
+
+impl<K, V, C: Comparator<K>> ExactSizeIterator for BufferedIter<'_, K, V, C> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.base.len() + self.tail.len()
+    }
+}
+
+impl<K, V, C: Comparator<K>> FusedIterator for BufferedIter<'_, K, V, C> {}
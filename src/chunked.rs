@@ -0,0 +1,704 @@
+use crate::extend::{merge_sorted, sort_dedup};
+use crate::{
+    Comparable, Comparator, IntoIter, Natural, VecBTreeMap, equal_range, range_indices, vec,
+};
+use alloc::vec::Vec;
+use allocator_api2::vec::Vec as AllocVec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator, Iterator};
+use core::mem;
+use core::ops::{self, RangeBounds};
+use core::slice;
+
+/// The maximum number of key-value pairs in a chunk.
+const CHUNK_LEN: usize = 512;
+
+/// The number of key-value pairs per chunk when building a map from sorted pairs,
+/// leaving room for later inserts.
+const FILL_LEN: usize = CHUNK_LEN / 4 * 3;
+
+/// A [`VecBTreeMap`] that stores its key-value pairs in a list of bounded sorted chunks,
+/// like the leaves of a B+ tree.
+///
+/// Inserting into or removing from a [`VecBTreeMap`] moves every pair after the key,
+/// which gets slow for large maps. This map only moves the pairs within one chunk of at
+/// most 512 pairs, plus a few bookkeeping words per chunk. Lookups first binary search
+/// a contiguous copy of the last key of every chunk, then the chunk itself, which is
+/// why inserting and removing keys requires `K: Clone`.
+///
+/// Iteration stays sorted, and pairs can still be accessed by position.
+///
+/// # Examples
+///
+/// ```
+/// use vec_btree_map::ChunkedVecBTreeMap;
+///
+/// let mut map = ChunkedVecBTreeMap::new();
+/// for i in (0..10_000).rev() {
+///     map.insert(i, i * 2);
+/// }
+/// assert_eq!(map.get(&4242), Some(&8484));
+/// assert_eq!(map.get_index(600), Some((&600, &1200)));
+/// assert!(map.range(10..20).map(|(k, _)| *k).eq(10..20));
+/// ```
+pub struct ChunkedVecBTreeMap<K, V, C = Natural> {
+    /// Sorted and non-empty chunks.
+    chunks: Vec<Vec<(K, V)>>,
+    /// The position of the first pair of each chunk.
+    starts: Vec<usize>,
+    /// The last key of each chunk, kept next to each other for the search.
+    maxes: Vec<K>,
+    len: usize,
+    cmp: C,
+}
+
+impl<K, V, C: Default> Default for ChunkedVecBTreeMap<K, V, C> {
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<K, V> ChunkedVecBTreeMap<K, V> {
+    /// Constructs a new, empty `ChunkedVecBTreeMap<K, V>`.
+    ///
+    /// The map will not allocate until it is first inserted into.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_comparator(Natural)
+    }
+}
+
+impl<K, V, C> ChunkedVecBTreeMap<K, V, C> {
+    /// Constructs a new, empty `ChunkedVecBTreeMap<K, V, C>` which orders its keys with `cmp`.
+    #[inline]
+    #[must_use]
+    pub const fn with_comparator(cmp: C) -> Self {
+        Self {
+            chunks: Vec::new(),
+            starts: Vec::new(),
+            maxes: Vec::new(),
+            len: 0,
+            cmp,
+        }
+    }
+
+    /// Returns a reference to the map's [`Comparator`].
+    #[inline]
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /// Returns the number of key-value pairs in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the map contains no key-value pairs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Clears the map, removing all key-value pairs.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+        self.starts.clear();
+        self.maxes.clear();
+        self.len = 0;
+    }
+
+    /// Returns the chunk and the position within that chunk of the pair at `index`,
+    /// which may be one past the last pair.
+    #[inline]
+    fn locate(&self, index: usize) -> (usize, usize) {
+        let c = self
+            .starts
+            .partition_point(|&s| s <= index)
+            .saturating_sub(1);
+        (c, index - self.starts.get(c).unwrap_or(&0))
+    }
+
+    /// Returns the key-value pair at the given position in key order, or [`None`] if
+    /// `index` is out of bounds.
+    ///
+    /// This takes `O(log n)` time.
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        if index >= self.len {
+            return None;
+        }
+        let (c, i) = self.locate(index);
+        let e = &self.chunks[c][i];
+        Some((&e.0, &e.1))
+    }
+
+    /// Returns the key-value pair at the given position in key order, with a mutable
+    /// reference to the value, or [`None`] if `index` is out of bounds.
+    #[inline]
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        if index >= self.len {
+            return None;
+        }
+        let (c, i) = self.locate(index);
+        let e = &mut self.chunks[c][i];
+        Some((&e.0, &mut e.1))
+    }
+
+    /// Gets an iterator over the key-value pairs at the given positions.
+    #[inline]
+    fn iter_indices(&self, indices: ops::Range<usize>) -> ChunkedIter<'_, K, V> {
+        if indices.is_empty() {
+            return ChunkedIter::default();
+        }
+        let (first, i) = self.locate(indices.start);
+        let (last, j) = self.locate(indices.end);
+        let len = indices.len();
+        if first == last {
+            return ChunkedIter::new(self.chunks[first][i..j].iter(), [].iter(), [].iter(), len);
+        }
+        ChunkedIter::new(
+            self.chunks[first][i..].iter(),
+            self.chunks[first + 1..last].iter(),
+            self.chunks[last][..j].iter(),
+            len,
+        )
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    #[inline]
+    pub fn iter(&self) -> ChunkedIter<'_, K, V> {
+        self.iter_indices(0..self.len)
+    }
+
+    /// Merges chunk `c` with a neighbour if both have become small, and drops it if it
+    /// has become empty.
+    fn rebalance(&mut self, c: usize) {
+        if self.chunks[c].is_empty() {
+            self.chunks.remove(c);
+            self.starts.remove(c);
+            self.maxes.remove(c);
+            return;
+        }
+        let c = if c + 1 < self.chunks.len() {
+            c
+        } else if c > 0 {
+            c - 1
+        } else {
+            return;
+        };
+        if self.chunks[c].len() + self.chunks[c + 1].len() <= CHUNK_LEN / 2 {
+            let next = self.chunks.remove(c + 1);
+            self.starts.remove(c + 1);
+            // The merged chunk ends with the last key of `next`.
+            self.maxes.remove(c);
+            self.chunks[c].extend(next);
+        }
+    }
+
+    /// Converts the map into a [`VecBTreeMap`].
+    ///
+    /// This takes `O(n)` time and reallocates.
+    #[inline]
+    #[must_use]
+    pub fn into_map(self) -> VecBTreeMap<K, V, C> {
+        let mut base = Vec::with_capacity(self.len);
+        self.chunks.into_iter().for_each(|c| base.extend(c));
+        VecBTreeMap {
+            base: vec::from_std(base),
+            cmp: self.cmp,
+        }
+    }
+}
+
+impl<K, V, C> ChunkedVecBTreeMap<K, V, C>
+where
+    C: Comparator<K>,
+{
+    /// Returns the chunk that holds the given key, or would hold it if it were inserted,
+    /// together with the result of searching that chunk.
+    #[inline]
+    fn search<Q>(&self, k: &Q) -> (usize, Result<usize, usize>)
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let c = self
+            .maxes
+            .partition_point(|m| k.compare(m, &self.cmp).is_gt())
+            .min(self.chunks.len().saturating_sub(1));
+        match self.chunks.get(c) {
+            Some(chunk) => (
+                c,
+                chunk.binary_search_by(|e| k.compare(&e.0, &self.cmp).reverse()),
+            ),
+            None => (0, Err(0)),
+        }
+    }
+
+    /// Returns the positions of all keys that compare equal to `k`.
+    ///
+    /// Such keys may span several chunks, so the first chunk whose last key is not less
+    /// than `k` holds the start, and the first chunk whose last key is greater holds the end.
+    #[inline]
+    fn equal_range<Q>(&self, k: &Q) -> ops::Range<usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let position = |c: usize, end: bool| match self.chunks.get(c) {
+            Some(chunk) => {
+                let run = equal_range(chunk, |e| k.compare(&e.0, &self.cmp).reverse());
+                self.starts[c] + if end { run.end } else { run.start }
+            }
+            None => self.len,
+        };
+        let first = self
+            .maxes
+            .partition_point(|m| k.compare(m, &self.cmp).is_gt());
+        let last = self
+            .maxes
+            .partition_point(|m| k.compare(m, &self.cmp).is_ge());
+        position(first, false)..position(last, true)
+    }
+
+    /// Binary searches this map for a given key.
+    ///
+    /// If the key is found then [`Result::Ok`] is returned, containing the
+    /// position of the matching key. If the key is not found then
+    /// [`Result::Err`] is returned, containing the position where the
+    /// key-value pair could be inserted while maintaining sorted order.
+    #[inline]
+    pub fn binary_search<Q>(&self, k: &Q) -> Result<usize, usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let (c, r) = self.search(k);
+        let start = self.starts.get(c).unwrap_or(&0);
+        r.map(|i| start + i).map_err(|i| start + i)
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.get_key_value(k).map(|(_, v)| v)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        match self.search(k) {
+            (c, Ok(i)) => Some(&mut self.chunks[c][i].1),
+            (_, Err(_)) => None,
+        }
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        match self.search(k) {
+            (c, Ok(i)) => Some((&self.chunks[c][i].0, &self.chunks[c][i].1)),
+            (_, Err(_)) => None,
+        }
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.search(k).1.is_ok()
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of key-value pairs in the map.
+    ///
+    /// See [`VecBTreeMap::range`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end` and the map contains a key within `end..=start`.
+    #[inline]
+    pub fn range<Q, R>(&self, range: R) -> ChunkedIter<'_, K, V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        self.iter_indices(range_indices(&range, self.len, |k| self.equal_range(k)))
+    }
+}
+
+impl<K, V, C> ChunkedVecBTreeMap<K, V, C>
+where
+    K: Clone,
+    C: Comparator<K>,
+{
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, [`None`] is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned. The key is not updated.
+    ///
+    /// A full chunk is split in half before inserting into it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        let (mut c, mut i) = match self.search(&k) {
+            (c, Ok(i)) => return Some(mem::replace(&mut self.chunks[c][i].1, v)),
+            (c, Err(i)) => (c, i),
+        };
+        if self.chunks.is_empty() {
+            self.chunks.push(Vec::with_capacity(CHUNK_LEN));
+            self.starts.push(0);
+            self.maxes.push(k.clone());
+        } else {
+            if self.chunks[c].len() == CHUNK_LEN {
+                let mut right = Vec::with_capacity(CHUNK_LEN);
+                right.extend(self.chunks[c].drain(CHUNK_LEN / 2..));
+                self.chunks.insert(c + 1, right);
+                self.starts.insert(c + 1, self.starts[c] + CHUNK_LEN / 2);
+                let max = self.chunks[c][CHUNK_LEN / 2 - 1].0.clone();
+                let right_max = mem::replace(&mut self.maxes[c], max);
+                self.maxes.insert(c + 1, right_max);
+                if i > CHUNK_LEN / 2 {
+                    c += 1;
+                    i -= CHUNK_LEN / 2;
+                }
+            }
+            if i == self.chunks[c].len() {
+                self.maxes[c] = k.clone();
+            }
+        }
+        self.chunks[c].insert(i, (k, v));
+        self.starts[c + 1..].iter_mut().for_each(|s| *s += 1);
+        self.len += 1;
+        None
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.remove_entry(k).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn remove_entry<Q>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let (c, i) = match self.search(k) {
+            (c, Ok(i)) => (c, i),
+            (_, Err(_)) => return None,
+        };
+        let e = self.chunks[c].remove(i);
+        if i == self.chunks[c].len()
+            && let Some(last) = self.chunks[c].last()
+        {
+            self.maxes[c] = last.0.clone();
+        }
+        self.starts[c + 1..].iter_mut().for_each(|s| *s -= 1);
+        self.len -= 1;
+        self.rebalance(c);
+        Some(e)
+    }
+}
+
+impl<K: Clone, V: Clone, C: Clone> Clone for ChunkedVecBTreeMap<K, V, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            chunks: self.chunks.clone(),
+            starts: self.starts.clone(),
+            maxes: self.maxes.clone(),
+            len: self.len,
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+impl<K: PartialEq, V: PartialEq, C> PartialEq for ChunkedVecBTreeMap<K, V, C> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.len == other.len && self.iter().eq(other.iter())
+    }
+}
+
+impl<K: Eq, V: Eq, C> Eq for ChunkedVecBTreeMap<K, V, C> {}
+
+impl<K: Debug, V: Debug, C> Debug for ChunkedVecBTreeMap<K, V, C> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, C> FromIterator<(K, V)> for ChunkedVecBTreeMap<K, V, C>
+where
+    K: Clone,
+    C: Comparator<K> + Default,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        VecBTreeMap::from_iter(iter).into()
+    }
+}
+
+impl<K: Clone, V, C: Comparator<K>> Extend<(K, V)> for ChunkedVecBTreeMap<K, V, C> {
+    /// Sorts the new pairs, then merges them into the chunks they belong to in a single
+    /// pass, splitting every chunk that grows too large.
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let mut other: AllocVec<_> = iter.into_iter().collect();
+        if other.is_empty() {
+            return;
+        }
+        sort_dedup(&mut other, &self.cmp);
+        let mut other = other.into_iter();
+        let old = mem::take(&mut self.chunks);
+        let last = old.len().saturating_sub(1);
+        for (c, chunk) in old.into_iter().enumerate() {
+            // The last chunk takes all remaining pairs, every other chunk those up to its last key.
+            let n = if c == last {
+                other.len()
+            } else {
+                let max = &self.maxes[c];
+                other
+                    .as_slice()
+                    .partition_point(|e| self.cmp.compare(&e.0, max).is_le())
+            };
+            if n == 0 {
+                self.chunks.push(chunk);
+                continue;
+            }
+            let mut merged = Vec::with_capacity(chunk.len() + n);
+            let resolve = |(k, _), (_, v)| (k, v);
+            let batch = other.by_ref().take(n);
+            merge_sorted(
+                chunk,
+                batch,
+                |e| &e.0,
+                &self.cmp,
+                resolve,
+                |e| merged.push(e),
+            );
+            if merged.len() <= CHUNK_LEN {
+                self.chunks.push(merged);
+            } else {
+                fill(&mut self.chunks, merged);
+            }
+        }
+        fill(&mut self.chunks, other);
+        self.reindex();
+    }
+}
+
+impl<K: Clone, V, C> ChunkedVecBTreeMap<K, V, C> {
+    /// Recomputes the position of the first pair, the last key and the length of every chunk.
+    fn reindex(&mut self) {
+        self.starts.clear();
+        self.maxes.clear();
+        let mut start = 0;
+        for chunk in &self.chunks {
+            self.starts.push(start);
+            self.maxes.push(chunk[chunk.len() - 1].0.clone());
+            start += chunk.len();
+        }
+        self.len = start;
+    }
+}
+
+/// Appends the sorted `pairs` to `chunks` in new chunks of `FILL_LEN` pairs.
+fn fill<T>(chunks: &mut Vec<Vec<T>>, pairs: impl IntoIterator<Item = T>) {
+    let mut pairs = pairs.into_iter().peekable();
+    while pairs.peek().is_some() {
+        let mut chunk = Vec::with_capacity(CHUNK_LEN);
+        chunk.extend(pairs.by_ref().take(FILL_LEN));
+        chunks.push(chunk);
+    }
+}
+
+impl<K: Clone, V, C> From<VecBTreeMap<K, V, C>> for ChunkedVecBTreeMap<K, V, C> {
+    #[inline]
+    fn from(map: VecBTreeMap<K, V, C>) -> Self {
+        let mut chunked = Self::with_comparator(map.cmp);
+        fill(&mut chunked.chunks, map.base);
+        chunked.reindex();
+        chunked
+    }
+}
+
+impl<K, V, C> From<ChunkedVecBTreeMap<K, V, C>> for VecBTreeMap<K, V, C> {
+    #[inline]
+    fn from(map: ChunkedVecBTreeMap<K, V, C>) -> Self {
+        map.into_map()
+    }
+}
+
+impl<K, V, C> IntoIterator for ChunkedVecBTreeMap<K, V, C> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_map().into_iter()
+    }
+}
+
+impl<'a, K, V, C> IntoIterator for &'a ChunkedVecBTreeMap<K, V, C> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = ChunkedIter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a [`ChunkedVecBTreeMap`], sorted by key.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct ChunkedIter<'a, K, V> {
+    front: slice::Iter<'a, (K, V)>,
+    chunks: slice::Iter<'a, Vec<(K, V)>>,
+    back: slice::Iter<'a, (K, V)>,
+    len: usize,
+}
+
+impl<'a, K, V> ChunkedIter<'a, K, V> {
+    #[inline]
+    fn new(
+        front: slice::Iter<'a, (K, V)>,
+        chunks: slice::Iter<'a, Vec<(K, V)>>,
+        back: slice::Iter<'a, (K, V)>,
+        len: usize,
+    ) -> Self {
+        Self {
+            front,
+            chunks,
+            back,
+            len,
+        }
+    }
+}
+
+impl<K, V> Default for ChunkedIter<'_, K, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new([].iter(), [].iter(), [].iter(), 0)
+    }
+}
+
+impl<K, V> Clone for ChunkedIter<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(
+            self.front.clone(),
+            self.chunks.clone(),
+            self.back.clone(),
+            self.len,
+        )
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for ChunkedIter<'_, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for ChunkedIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let e = loop {
+            if let Some(e) = self.front.next() {
+                break e;
+            }
+            match self.chunks.next() {
+                Some(chunk) => self.front = chunk.iter(),
+                None => break self.back.next()?,
+            }
+        };
+        self.len -= 1;
+        Some((&e.0, &e.1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl<K, V> DoubleEndedIterator for ChunkedIter<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let e = loop {
+            if let Some(e) = self.back.next_back() {
+                break e;
+            }
+            match self.chunks.next_back() {
+                Some(chunk) => self.back = chunk.iter(),
+                None => break self.front.next_back()?,
+            }
+        };
+        self.len -= 1;
+        Some((&e.0, &e.1))
+    }
+}
+
+impl<K, V> ExactSizeIterator for ChunkedIter<'_, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl<K, V> FusedIterator for ChunkedIter<'_, K, V> {}
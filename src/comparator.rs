@@ -0,0 +1,136 @@
+use core::borrow::Borrow;
+use core::cmp::Ordering;
+
+/// A total order over `T`, used by a [`VecBTreeMap`](crate::VecBTreeMap) to keep its keys sorted.
+///
+/// Besides the built-in [`Natural`] and [`Reverse`] comparators, every closure or function
+/// of the form `Fn(&T, &T) -> Ordering` is a comparator.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// use vec_btree_map::VecBTreeMap;
+///
+/// let mut map = VecBTreeMap::with_comparator(|a: &&str, b: &&str| {
+///     a.to_lowercase().cmp(&b.to_lowercase())
+/// });
+/// map.insert("b", 1);
+/// map.insert("A", 2);
+/// map.insert("B", 3);
+///
+/// assert_eq!(map.into_vec(), [("A", 2), ("b", 3)]);
+/// ```
+pub trait Comparator<T: ?Sized> {
+    /// Returns an [`Ordering`] between `a` and `b`.
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// Orders keys by their [`Ord`] implementation. This is the default comparator.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Natural;
+
+impl<T: Ord + ?Sized> Comparator<T> for Natural {
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Reverses the order of the wrapped comparator, which defaults to [`Natural`].
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// use vec_btree_map::{Natural, Reverse, VecBTreeMap};
+///
+/// let mut map = VecBTreeMap::with_comparator(Reverse(Natural));
+/// map.insert(1, "a");
+/// map.insert(3, "c");
+/// map.insert(2, "b");
+///
+/// assert_eq!(map.into_vec(), [(3, "c"), (2, "b"), (1, "a")]);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Reverse<C = Natural>(pub C);
+
+impl<T: ?Sized, C: Comparator<T>> Comparator<T> for Reverse<C> {
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self.0.compare(b, a)
+    }
+}
+
+impl<T: ?Sized, F> Comparator<T> for F
+where
+    F: Fn(&T, &T) -> Ordering,
+{
+    #[inline]
+    fn compare(&self, a: &T, b: &T) -> Ordering {
+        self(a, b)
+    }
+}
+
+/// A key that can be looked up in a map whose keys are of type `K`
+/// and ordered by the comparator `C`.
+///
+/// This is implemented for every borrowed form `Q` of the key type, i.e. whenever `K: Borrow<Q>`.
+/// Implement it for your own types to look up keys without constructing a `K`,
+/// e.g. to search a composite key by a borrowed composite or by its prefix.
+/// The ordering *must* be consistent with the ordering of the keys.
+///
+/// Such a key may compare equal to several keys of the map. Ranges, cursors and
+/// `split_off` then cover all of them, while single-key lookups like `get` and
+/// `remove` act on any one of them.
+///
+/// # Examples
+///
+#[cfg_attr(feature = "alloc", doc = "```")]
+#[cfg_attr(not(feature = "alloc"), doc = "```ignore")]
+/// use std::cmp::Ordering;
+/// use vec_btree_map::{Comparable, Natural, VecBTreeMap};
+///
+/// struct Key<'a>(&'a str, u32);
+///
+/// impl Comparable<(String, u32)> for Key<'_> {
+///     fn compare(&self, key: &(String, u32), _: &Natural) -> Ordering {
+///         (self.0, self.1).cmp(&(key.0.as_str(), key.1))
+///     }
+/// }
+///
+/// /// Matches every key that starts with the given string.
+/// struct Prefix<'a>(&'a str);
+///
+/// impl Comparable<(String, u32)> for Prefix<'_> {
+///     fn compare(&self, key: &(String, u32), _: &Natural) -> Ordering {
+///         self.0.cmp(key.0.as_str())
+///     }
+/// }
+///
+/// let mut map = VecBTreeMap::new();
+/// map.insert(("a".to_string(), 1), "a1");
+/// map.insert(("b".to_string(), 1), "b1");
+/// map.insert(("b".to_string(), 2), "b2");
+///
+/// assert_eq!(map.get(&Key("b", 2)), Some(&"b2"));
+/// assert!(map.binary_search(&Prefix("b")).is_ok());
+/// assert!(map.binary_search(&Prefix("c")).is_err());
+/// assert_eq!(map.range(Prefix("b")..=Prefix("b")).count(), 2);
+/// ```
+pub trait Comparable<K: ?Sized, C = Natural> {
+    /// Returns an [`Ordering`] between `self` and `key` in the order defined by `cmp`.
+    fn compare(&self, key: &K, cmp: &C) -> Ordering;
+}
+
+impl<Q, K, C> Comparable<K, C> for Q
+where
+    Q: ?Sized,
+    K: Borrow<Q> + ?Sized,
+    C: Comparator<Q>,
+{
+    #[inline]
+    fn compare(&self, key: &K, cmp: &C) -> Ordering {
+        cmp.compare(self, key.borrow())
+    }
+}
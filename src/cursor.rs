@@ -0,0 +1,348 @@
+use crate::{Comparator, Natural, VecBTreeMap};
+use allocator_api2::alloc::{Allocator, Global};
+use core::fmt::{Debug, Formatter, Result};
+
+/// A cursor over a [`VecBTreeMap`].
+///
+/// A `Cursor` is like an iterator, except that it can freely seek back-and-forth.
+///
+/// Cursors always point to an element in the map, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields [`None`] between
+/// the last and first elements of the map.
+///
+/// A `Cursor` is created with the [`VecBTreeMap::lower_bound`] and
+/// [`VecBTreeMap::upper_bound`] methods.
+pub struct Cursor<'a, K, V> {
+    base: &'a [(K, V)],
+    index: usize,
+}
+
+impl<K, V> Clone for Cursor<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.base, self.index)
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for Cursor<'_, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("Cursor").field(&self.key_value()).finish()
+    }
+}
+
+impl<'a, K, V> Cursor<'a, K, V> {
+    #[inline]
+    pub(super) const fn new(base: &'a [(K, V)], index: usize) -> Self {
+        Self { base, index }
+    }
+
+    /// Moves the cursor to the next element of the map.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the map. If it is pointing to the last
+    /// element of the map then this will move it to the "ghost" non-element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.index = next_index(self.index, self.base.len());
+    }
+
+    /// Moves the cursor to the previous element of the map.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the map. If it is pointing to the first
+    /// element of the map then this will move it to the "ghost" non-element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.index = prev_index(self.index, self.base.len());
+    }
+
+    /// Returns the position of the element that the cursor is currently pointing to.
+    ///
+    /// This returns [`None`] if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.base.len()).then_some(self.index)
+    }
+
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns [`None`] if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[inline]
+    pub fn key(&self) -> Option<&'a K> {
+        self.key_value().map(|(k, _)| k)
+    }
+
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns [`None`] if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[inline]
+    pub fn value(&self) -> Option<&'a V> {
+        self.key_value().map(|(_, v)| v)
+    }
+
+    /// Returns a reference to the key and value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns [`None`] if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[inline]
+    pub fn key_value(&self) -> Option<(&'a K, &'a V)> {
+        self.base.get(self.index).map(|e| (&e.0, &e.1))
+    }
+
+    /// Returns a reference to the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the map. If it is pointing to the last
+    /// element of the map then this returns [`None`].
+    #[inline]
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        let i = next_index(self.index, self.base.len());
+        self.base.get(i).map(|e| (&e.0, &e.1))
+    }
+
+    /// Returns a reference to the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the map. If it is pointing to the first
+    /// element of the map then this returns [`None`].
+    #[inline]
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        let i = prev_index(self.index, self.base.len());
+        self.base.get(i).map(|e| (&e.0, &e.1))
+    }
+}
+
+/// A cursor over a [`VecBTreeMap`] with editing operations.
+///
+/// A `CursorMut` is like an iterator, except that it can freely seek back-and-forth, and can
+/// safely mutate the map during iteration. This is because the lifetime of its yielded
+/// references is tied to its own lifetime, instead of just the underlying map. This means
+/// cursors cannot yield multiple elements at once.
+///
+/// Cursors always point to an element in the map, and index in a logically circular way.
+/// To accommodate this, there is a "ghost" non-element that yields [`None`] between
+/// the last and first elements of the map.
+///
+/// A `CursorMut` is created with the [`VecBTreeMap::lower_bound_mut`] and
+/// [`VecBTreeMap::upper_bound_mut`] methods.
+pub struct CursorMut<'a, K, V, C = Natural, A: Allocator = Global> {
+    map: &'a mut VecBTreeMap<K, V, C, A>,
+    index: usize,
+}
+
+impl<K: Debug, V: Debug, C, A: Allocator> Debug for CursorMut<'_, K, V, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("CursorMut").field(&self.key_value()).finish()
+    }
+}
+
+impl<'a, K, V, C, A: Allocator> CursorMut<'a, K, V, C, A> {
+    #[inline]
+    pub(super) fn new(map: &'a mut VecBTreeMap<K, V, C, A>, index: usize) -> Self {
+        Self { map, index }
+    }
+
+    /// Moves the cursor to the next element of the map.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the map. If it is pointing to the last
+    /// element of the map then this will move it to the "ghost" non-element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.index = next_index(self.index, self.map.base.len());
+    }
+
+    /// Moves the cursor to the previous element of the map.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the map. If it is pointing to the first
+    /// element of the map then this will move it to the "ghost" non-element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.index = prev_index(self.index, self.map.base.len());
+    }
+
+    /// Returns the position of the element that the cursor is currently pointing to.
+    ///
+    /// This returns [`None`] if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.map.base.len()).then_some(self.index)
+    }
+
+    /// Returns a reference to the key of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns [`None`] if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[inline]
+    pub fn key(&self) -> Option<&K> {
+        self.key_value().map(|(k, _)| k)
+    }
+
+    /// Returns a reference to the value of the element that the cursor is
+    /// currently pointing to.
+    ///
+    /// This returns [`None`] if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[inline]
+    pub fn value(&self) -> Option<&V> {
+        self.key_value().map(|(_, v)| v)
+    }
+
+    /// Returns a mutable reference to the value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns [`None`] if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[inline]
+    pub fn value_mut(&mut self) -> Option<&mut V> {
+        self.map.base.get_mut(self.index).map(|e| &mut e.1)
+    }
+
+    /// Returns a reference to the key and value of the element that the cursor
+    /// is currently pointing to.
+    ///
+    /// This returns [`None`] if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[inline]
+    pub fn key_value(&self) -> Option<(&K, &V)> {
+        self.map.base.get(self.index).map(|e| (&e.0, &e.1))
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the element that the cursor is currently pointing to.
+    ///
+    /// This returns [`None`] if the cursor is currently pointing to the
+    /// "ghost" non-element.
+    #[inline]
+    pub fn key_value_mut(&mut self) -> Option<(&K, &mut V)> {
+        self.map.base.get_mut(self.index).map(|e| (&e.0, &mut e.1))
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the next element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the first element of the map. If it is pointing to the last
+    /// element of the map then this returns [`None`].
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        let i = next_index(self.index, self.map.base.len());
+        self.map.base.get_mut(i).map(|e| (&e.0, &mut e.1))
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the previous element.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this returns
+    /// the last element of the map. If it is pointing to the first
+    /// element of the map then this returns [`None`].
+    #[inline]
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        let i = prev_index(self.index, self.map.base.len());
+        self.map.base.get_mut(i).map(|e| (&e.0, &mut e.1))
+    }
+
+    /// Returns a read-only cursor pointing to the current element.
+    ///
+    /// The lifetime of the returned `Cursor` is bound to that of the
+    /// `CursorMut`, which means it cannot outlive the `CursorMut` and that the
+    /// `CursorMut` is frozen for the lifetime of the `Cursor`.
+    #[inline]
+    pub fn as_cursor(&self) -> Cursor<'_, K, V> {
+        Cursor::new(&self.map.base, self.index)
+    }
+
+    /// Removes the current element from the map.
+    ///
+    /// The element that was removed is returned, and the cursor is
+    /// moved to point to the next element in the map.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and [`None`] is returned. The cursor is not moved in this case.
+    #[inline]
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        (self.index < self.map.base.len()).then(|| self.map.base.remove(self.index))
+    }
+}
+
+impl<K, V, C: Comparator<K>, A: Allocator> CursorMut<'_, K, V, C, A> {
+    /// Inserts a new element into the map after the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the front of the map.
+    ///
+    /// If the map wouldn't be sorted anymore by inserting the key-value pair
+    /// at this position, [`Some`]`(K, V)` is returned and the map is left unchanged.
+    /// Otherwise [`None`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn insert_after(&mut self, k: K, v: V) -> Option<(K, V)> {
+        let len = self.map.base.len();
+        let at = if self.index < len { self.index + 1 } else { 0 };
+        if !self.fits(at, &k) {
+            return Some((k, v));
+        }
+        self.map.base.insert(at, (k, v));
+        if self.index == len {
+            self.index += 1;
+        }
+        None
+    }
+
+    /// Inserts a new element into the map before the current one.
+    ///
+    /// If the cursor is pointing at the "ghost" non-element then the new element is
+    /// inserted at the end of the map.
+    ///
+    /// If the map wouldn't be sorted anymore by inserting the key-value pair
+    /// at this position, [`Some`]`(K, V)` is returned and the map is left unchanged.
+    /// Otherwise [`None`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn insert_before(&mut self, k: K, v: V) -> Option<(K, V)> {
+        if !self.fits(self.index, &k) {
+            return Some((k, v));
+        }
+        self.map.base.insert(self.index, (k, v));
+        self.index += 1;
+        None
+    }
+
+    /// Returns `true` if `k` can be inserted at position `at` without breaking the order.
+    #[inline]
+    fn fits(&self, at: usize, k: &K) -> bool {
+        let VecBTreeMap { base, cmp } = &*self.map;
+        at.checked_sub(1)
+            .is_none_or(|i| cmp.compare(&base[i].0, k).is_lt())
+            && base.get(at).is_none_or(|e| cmp.compare(k, &e.0).is_lt())
+    }
+}
+
+/// Returns the position after `index` in a circular map of length `len`.
+#[inline]
+pub(crate) const fn next_index(index: usize, len: usize) -> usize {
+    if index >= len { 0 } else { index + 1 }
+}
+
+/// Returns the position before `index` in a circular map of length `len`.
+#[inline]
+pub(crate) const fn prev_index(index: usize, len: usize) -> usize {
+    if index == 0 { len } else { index - 1 }
+}
@@ -1,9 +1,9 @@
 use crate::VecBTreeMap;
-use alloc::vec::Vec;
+use allocator_api2::alloc::Allocator;
 use core::ops::Deref;
 
-impl<K, V> Deref for VecBTreeMap<K, V> {
-    type Target = Vec<(K, V)>;
+impl<K, V, C, A: Allocator> Deref for VecBTreeMap<K, V, C, A> {
+    type Target = [(K, V)];
 
     #[inline]
     fn deref(&self) -> &Self::Target {
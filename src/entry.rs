@@ -0,0 +1,350 @@
+use crate::{Natural, VecBTreeMap};
+use allocator_api2::alloc::{Allocator, Global};
+use core::fmt::{Debug, Formatter, Result};
+use core::mem;
+
+/// A view into a single entry in a map, which may either be vacant or occupied.
+///
+/// This `enum` is constructed from the [`entry`] method on [`VecBTreeMap`].
+///
+/// [`entry`]: VecBTreeMap::entry
+pub enum Entry<'a, K, V, C = Natural, A: Allocator = Global> {
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'a, K, V, C, A>),
+    /// A vacant entry.
+    Vacant(VacantEntry<'a, K, V, C, A>),
+}
+
+impl<K: Debug, V: Debug, C, A: Allocator> Debug for Entry<'_, K, V, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Occupied(e) => f.debug_tuple("Entry").field(e).finish(),
+            Self::Vacant(e) => f.debug_tuple("Entry").field(e).finish(),
+        }
+    }
+}
+
+impl<'a, K, V, C, A: Allocator> Entry<'a, K, V, C, A> {
+    /// Ensures a value is in the entry by inserting the default if empty, and returns
+    /// a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.entry("a").or_insert(1);
+    ///
+    /// assert_eq!(map.get("a"), Some(&1));
+    /// ```
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => e.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// let s = "hoho".to_string();
+    ///
+    /// map.entry("a").or_insert_with(|| s);
+    ///
+    /// assert_eq!(map.get("a"), Some(&"hoho".to_string()));
+    /// ```
+    #[inline]
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => e.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of the default function.
+    /// The function is passed a reference to the key that was moved into the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    ///
+    /// map.entry("abc").or_insert_with_key(|key| key.len());
+    ///
+    /// assert_eq!(map.get("abc"), Some(&3));
+    /// ```
+    #[inline]
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => {
+                let v = default(e.key());
+                e.insert(v)
+            }
+        }
+    }
+
+    /// Returns a reference to this entry's key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map: VecBTreeMap<&str, usize> = VecBTreeMap::new();
+    /// assert_eq!(map.entry("a").key(), &"a");
+    /// ```
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Occupied(e) => e.key(),
+            Self::Vacant(e) => e.key(),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    ///
+    /// map.entry("a").and_modify(|e| *e += 1).or_insert(42);
+    /// assert_eq!(map.get("a"), Some(&42));
+    ///
+    /// map.entry("a").and_modify(|e| *e += 1).or_insert(42);
+    /// assert_eq!(map.get("a"), Some(&43));
+    /// ```
+    #[inline]
+    pub fn and_modify<F: FnOnce(&mut V)>(self, f: F) -> Self {
+        match self {
+            Self::Occupied(mut e) => {
+                f(e.get_mut());
+                Self::Occupied(e)
+            }
+            Self::Vacant(e) => Self::Vacant(e),
+        }
+    }
+}
+
+impl<'a, K, V: Default, C, A: Allocator> Entry<'a, K, V, C, A> {
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map: VecBTreeMap<&str, Option<usize>> = VecBTreeMap::new();
+    /// map.entry("a").or_default();
+    ///
+    /// assert_eq!(map.get("a"), Some(&None));
+    /// ```
+    #[inline]
+    pub fn or_default(self) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => e.insert(V::default()),
+        }
+    }
+}
+
+/// A view into an occupied entry in a [`VecBTreeMap`].
+/// It is part of the [`Entry`] enum.
+pub struct OccupiedEntry<'a, K, V, C = Natural, A: Allocator = Global> {
+    map: &'a mut VecBTreeMap<K, V, C, A>,
+    index: usize,
+}
+
+impl<K: Debug, V: Debug, C, A: Allocator> Debug for OccupiedEntry<'_, K, V, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("OccupiedEntry")
+            .field("key", self.key())
+            .field("value", self.get())
+            .finish()
+    }
+}
+
+impl<'a, K, V, C, A: Allocator> OccupiedEntry<'a, K, V, C, A> {
+    #[inline]
+    pub(super) fn new(map: &'a mut VecBTreeMap<K, V, C, A>, index: usize) -> Self {
+        Self { map, index }
+    }
+
+    /// Gets a reference to the key in the entry.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.map.base[self.index].0
+    }
+
+    /// Gets the position of the entry in the map.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Gets a reference to the value in the entry.
+    #[inline]
+    pub fn get(&self) -> &V {
+        &self.map.base[self.index].1
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    ///
+    /// If you need a reference to the `OccupiedEntry` that may outlive the
+    /// destruction of the `Entry` value, see [`into_mut`](Self::into_mut).
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.base[self.index].1
+    }
+
+    /// Converts the entry into a mutable reference to its value.
+    ///
+    /// If you need multiple references to the `OccupiedEntry`, see [`get_mut`](Self::get_mut).
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.base[self.index].1
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::{Entry, VecBTreeMap};
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// if let Entry::Occupied(mut o) = map.entry("a") {
+    ///     assert_eq!(o.insert(2), 1);
+    /// }
+    /// assert_eq!(map.get("a"), Some(&2));
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Takes the value of the entry out of the map, and returns it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::{Entry, VecBTreeMap};
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// if let Entry::Occupied(o) = map.entry("a") {
+    ///     assert_eq!(o.remove(), 1);
+    /// }
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Takes the key-value pair of the entry out of the map, and returns it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::{Entry, VecBTreeMap};
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// if let Entry::Occupied(o) = map.entry("a") {
+    ///     assert_eq!(o.remove_entry(), ("a", 1));
+    /// }
+    /// assert!(map.is_empty());
+    /// ```
+    #[inline]
+    pub fn remove_entry(self) -> (K, V) {
+        self.map.base.remove(self.index)
+    }
+}
+
+/// A view into a vacant entry in a [`VecBTreeMap`].
+/// It is part of the [`Entry`] enum.
+pub struct VacantEntry<'a, K, V, C = Natural, A: Allocator = Global> {
+    map: &'a mut VecBTreeMap<K, V, C, A>,
+    key: K,
+    index: usize,
+}
+
+impl<K: Debug, V, C, A: Allocator> Debug for VacantEntry<'_, K, V, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("VacantEntry").field(self.key()).finish()
+    }
+}
+
+impl<'a, K, V, C, A: Allocator> VacantEntry<'a, K, V, C, A> {
+    #[inline]
+    pub(super) fn new(map: &'a mut VecBTreeMap<K, V, C, A>, key: K, index: usize) -> Self {
+        Self { map, key, index }
+    }
+
+    /// Gets a reference to the key that would be used when inserting a value
+    /// through the `VacantEntry`.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Gets the position the entry would be inserted at.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Takes ownership of the key.
+    #[inline]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Sets the value of the entry with the `VacantEntry`'s key,
+    /// and returns a mutable reference to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::{Entry, VecBTreeMap};
+    ///
+    /// let mut map = VecBTreeMap::new();
+    ///
+    /// if let Entry::Vacant(v) = map.entry("a") {
+    ///     *v.insert(1) += 1;
+    /// }
+    /// assert_eq!(map.get("a"), Some(&2));
+    /// ```
+    #[inline]
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.map.base.insert(self.index, (self.key, value));
+        &mut self.map.base[self.index].1
+    }
+}
@@ -0,0 +1,195 @@
+#[cfg(feature = "alloc")]
+use allocator_api2::alloc::{Allocator, Global};
+#[cfg(feature = "alloc")]
+pub use allocator_api2::collections::TryReserveError;
+#[cfg(feature = "alloc")]
+use allocator_api2::vec::Vec;
+use core::error::Error;
+use core::fmt::{self, Debug, Display, Formatter};
+
+#[cfg(feature = "alloc")]
+/// The error returned by [`VecBTreeMap::try_from_sorted_vec`] when the keys of the
+/// given vec are not strictly increasing.
+///
+/// It hands the vec back unchanged, together with the position of the first
+/// key-value pair that breaks the order.
+///
+/// [`VecBTreeMap::try_from_sorted_vec`]: crate::VecBTreeMap::try_from_sorted_vec
+pub struct NotSorted<K, V, A: Allocator = Global> {
+    vec: Vec<(K, V), A>,
+    index: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> NotSorted<K, V, A> {
+    #[inline]
+    pub(crate) const fn new(vec: Vec<(K, V), A>, index: usize) -> Self {
+        Self { vec, index }
+    }
+
+    /// Returns the position of the first key that is not greater than its predecessor.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns a reference to the rejected vec.
+    #[inline]
+    pub fn as_vec(&self) -> &Vec<(K, V), A> {
+        &self.vec
+    }
+
+    /// Takes back ownership of the rejected vec.
+    #[inline]
+    pub fn into_vec(self) -> Vec<(K, V), A> {
+        self.vec
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Clone, V: Clone, A: Allocator + Clone> Clone for NotSorted<K, V, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.vec.clone(), self.index)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: PartialEq, V: PartialEq, A: Allocator> PartialEq for NotSorted<K, V, A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.vec == other.vec
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Eq, V: Eq, A: Allocator> Eq for NotSorted<K, V, A> {}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> Debug for NotSorted<K, V, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NotSorted")
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> Display for NotSorted<K, V, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "key at index {} is not greater than the previous key",
+            self.index
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> Error for NotSorted<K, V, A> {}
+
+/// The error returned by [`ArrayVecBTreeMap::insert`] when the map is full.
+///
+/// It hands back the rejected key-value pair.
+///
+/// [`ArrayVecBTreeMap::insert`]: crate::ArrayVecBTreeMap::insert
+#[derive(Clone, PartialEq, Eq)]
+pub struct CapacityError<K, V> {
+    pair: (K, V),
+}
+
+impl<K, V> CapacityError<K, V> {
+    #[inline]
+    pub(crate) const fn new(pair: (K, V)) -> Self {
+        Self { pair }
+    }
+
+    /// Returns a reference to the rejected key-value pair.
+    #[inline]
+    pub const fn as_inner(&self) -> &(K, V) {
+        &self.pair
+    }
+
+    /// Takes back ownership of the rejected key-value pair.
+    #[inline]
+    pub fn into_inner(self) -> (K, V) {
+        self.pair
+    }
+}
+
+impl<K, V> Debug for CapacityError<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CapacityError").finish_non_exhaustive()
+    }
+}
+
+impl<K, V> Display for CapacityError<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("map is at full capacity")
+    }
+}
+
+impl<K, V> Error for CapacityError<K, V> {}
+
+#[cfg(feature = "alloc")]
+/// The error returned by [`VecBTreeMap::try_insert`] when the map could not grow.
+///
+/// It hands back the rejected key-value pair, together with the allocation error.
+///
+/// [`VecBTreeMap::try_insert`]: crate::VecBTreeMap::try_insert
+#[derive(Clone, PartialEq, Eq)]
+pub struct TryInsertError<K, V> {
+    pair: (K, V),
+    error: TryReserveError,
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> TryInsertError<K, V> {
+    #[inline]
+    pub(crate) const fn new(pair: (K, V), error: TryReserveError) -> Self {
+        Self { pair, error }
+    }
+
+    /// Returns the allocation error that caused the insertion to fail.
+    #[inline]
+    pub const fn error(&self) -> &TryReserveError {
+        &self.error
+    }
+
+    /// Returns a reference to the rejected key-value pair.
+    #[inline]
+    pub const fn as_inner(&self) -> &(K, V) {
+        &self.pair
+    }
+
+    /// Takes back ownership of the rejected key-value pair.
+    #[inline]
+    pub fn into_inner(self) -> (K, V) {
+        self.pair
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> Debug for TryInsertError<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TryInsertError")
+            .field("error", &self.error)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> Display for TryInsertError<K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.error, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V> Error for TryInsertError<K, V> {}
@@ -0,0 +1,143 @@
+use crate::{Comparator, VecBTreeMap};
+use allocator_api2::alloc::Allocator;
+use allocator_api2::vec::Vec;
+use core::cmp::Ordering;
+use core::mem;
+
+impl<K, V, C, A> FromIterator<(K, V)> for VecBTreeMap<K, V, C, A>
+where
+    C: Comparator<K> + Default,
+    A: Allocator + Default,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let cmp = C::default();
+        let mut base = Vec::new_in(A::default());
+        base.extend(iter);
+        sort_dedup(&mut base, &cmp);
+        Self { base, cmp }
+    }
+}
+
+impl<K, V, C: Comparator<K>, A: Allocator + Clone> Extend<(K, V)> for VecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let mut other: Vec<_> = iter.into_iter().collect();
+        sort_dedup(&mut other, &self.cmp);
+        self.merge(other);
+    }
+}
+
+impl<'a, K, V, C, A> Extend<(&'a K, &'a V)> for VecBTreeMap<K, V, C, A>
+where
+    K: Copy,
+    V: Copy,
+    C: Comparator<K>,
+    A: Allocator + Clone,
+{
+    #[inline]
+    fn extend<I: IntoIterator<Item = (&'a K, &'a V)>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().map(|(&k, &v)| (k, v)));
+    }
+}
+
+/// Stable sorts `base` by key and removes duplicate keys, keeping the value
+/// that came last, just like repeated calls to [`VecBTreeMap::insert`] would.
+pub(crate) fn sort_dedup<K, V, C, A>(base: &mut Vec<(K, V), A>, cmp: &C)
+where
+    C: Comparator<K>,
+    A: Allocator,
+{
+    if base.is_sorted_by(|a, b| cmp.compare(&a.0, &b.0).is_lt()) {
+        return;
+    }
+    base.sort_by(|a, b| cmp.compare(&a.0, &b.0));
+    dedup(base, cmp);
+}
+
+/// Removes consecutive duplicate keys from the sorted `base`, keeping the first key
+/// but the value that came last.
+pub(crate) fn dedup<K, V, C, A>(base: &mut Vec<(K, V), A>, cmp: &C)
+where
+    C: Comparator<K>,
+    A: Allocator,
+{
+    base.dedup_by(|next, kept| {
+        if cmp.compare(&next.0, &kept.0).is_eq() {
+            // keep the first key but the last value
+            mem::swap(&mut next.1, &mut kept.1);
+            true
+        } else {
+            false
+        }
+    });
+}
+
+/// Merges the sorted and deduplicated `other` into `base` in `O(n + m)`.
+/// On equal keys the key of `base` is kept and the value of `other` wins.
+#[inline]
+pub(crate) fn merge<K, V, C, A, B>(base: &mut Vec<(K, V), A>, other: Vec<(K, V), B>, cmp: &C)
+where
+    C: Comparator<K>,
+    A: Allocator + Clone,
+    B: Allocator,
+{
+    merge_by(base, other, |e| &e.0, cmp, |(k, _), (_, v)| (k, v));
+}
+
+/// Merges the sorted and deduplicated `other` into `base` in `O(n + m)`, ordering the
+/// elements by the part `key` projects out of them. Two elements with equal keys are
+/// combined into one by `resolve`, which gets the element of `base` first.
+pub(crate) fn merge_by<T, Q, C, A, B>(
+    base: &mut Vec<T, A>,
+    other: Vec<T, B>,
+    key: impl Fn(&T) -> &Q,
+    cmp: &C,
+    resolve: impl FnMut(T, T) -> T,
+) where
+    Q: ?Sized,
+    C: Comparator<Q>,
+    A: Allocator + Clone,
+    B: Allocator,
+{
+    match (base.last(), other.first()) {
+        (_, None) => return,
+        (Some(last), Some(first)) if cmp.compare(key(last), key(first)).is_ge() => {}
+        _ => {
+            base.extend(other);
+            return;
+        }
+    }
+    let alloc = base.allocator().clone();
+    let mut merged = Vec::with_capacity_in(base.len() + other.len(), alloc.clone());
+    let left = mem::replace(base, Vec::new_in(alloc));
+    merge_sorted(left, other, key, cmp, resolve, |e| merged.push(e));
+    *base = merged;
+}
+
+/// Walks the sorted and deduplicated runs `left` and `right` in order and hands every
+/// element to `push`. Two elements with equal keys are combined into one by `resolve`,
+/// which gets the element of `left` first.
+pub(crate) fn merge_sorted<T, Q, C>(
+    left: impl IntoIterator<Item = T>,
+    right: impl IntoIterator<Item = T>,
+    key: impl Fn(&T) -> &Q,
+    cmp: &C,
+    mut resolve: impl FnMut(T, T) -> T,
+    mut push: impl FnMut(T),
+) where
+    Q: ?Sized,
+    C: Comparator<Q>,
+{
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        let e = match cmp.compare(key(l), key(r)) {
+            Ordering::Less => left.next(),
+            Ordering::Greater => right.next(),
+            Ordering::Equal => left.next().zip(right.next()).map(|(l, r)| resolve(l, r)),
+        };
+        e.into_iter().for_each(&mut push);
+    }
+    left.chain(right).for_each(push);
+}
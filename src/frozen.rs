@@ -0,0 +1,360 @@
+use crate::{Comparable, Comparator, Natural, VecBTreeMap};
+use alloc::vec::Vec;
+use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::vec::Vec as AllocVec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator, Iterator};
+use core::mem;
+use core::slice;
+
+/// An immutable map that stores its key-value pairs in Eytzinger (BFS) order.
+///
+/// The pair at position `i` has its children at `2i + 1` and `2i + 2`, so a lookup walks
+/// down an implicit binary tree whose top levels share the same few cache lines. The
+/// search loop has no data-dependent branches and prefetches the nodes four levels ahead,
+/// which can make it faster than a binary search over a sorted slice for large, read-mostly
+/// maps. Measure with the `frozen` bench before relying on it.
+///
+/// Keys cannot be inserted or removed. Use [`thaw`](Self::thaw) to get a [`VecBTreeMap`] back.
+///
+/// # Examples
+///
+/// ```
+/// use vec_btree_map::{FrozenVecBTreeMap, VecBTreeMap};
+///
+/// let map: VecBTreeMap<_, _> = [("c", 3), ("a", 1), ("b", 2)].into_iter().collect();
+/// let frozen = map.freeze();
+///
+/// assert_eq!(frozen.get("b"), Some(&2));
+/// assert!(frozen.iter().map(|(k, _)| *k).eq(["a", "b", "c"]));
+///
+/// let map = frozen.thaw();
+/// assert_eq!(map.into_vec(), [("a", 1), ("b", 2), ("c", 3)]);
+/// ```
+pub struct FrozenVecBTreeMap<K, V, C = Natural, A: Allocator = Global> {
+    base: AllocVec<(K, V), A>,
+    /// The position in `base` of each pair, in order by key.
+    order: Vec<usize>,
+    cmp: C,
+}
+
+/// Pushes the Eytzinger positions of a tree with `n` nodes to `order` in order by key,
+/// starting at the 1-based node `k`.
+fn in_order(k: usize, n: usize, order: &mut Vec<usize>) {
+    if k <= n {
+        in_order(2 * k, n, order);
+        order.push(k - 1);
+        in_order(2 * k + 1, n, order);
+    }
+}
+
+/// Moves the element at `src[i]` to position `i` for every `i`, in place.
+///
+/// Each cycle of the permutation is walked once with swaps; `src` is overwritten to mark
+/// the positions that are already in place.
+fn permute<T>(v: &mut [T], src: &mut [usize]) {
+    for start in 0..src.len() {
+        let mut i = start;
+        while src[i] != start {
+            let j = mem::replace(&mut src[i], i);
+            v.swap(i, j);
+            i = j;
+        }
+        src[i] = i;
+    }
+}
+
+/// Hints the CPU to start loading the pair at `i`, which may be out of bounds.
+#[inline(always)]
+fn prefetch<T>(base: &[T], i: usize) {
+    #[cfg(target_arch = "x86_64")]
+    {
+        use core::arch::x86_64::{_MM_HINT_T0, _mm_prefetch};
+        // SAFETY: prefetching is only a hint and never faults, even for addresses
+        // outside of the allocation.
+        unsafe { _mm_prefetch::<_MM_HINT_T0>(base.as_ptr().wrapping_add(i).cast()) };
+    }
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = (base, i);
+}
+
+impl<K, V, C, A: Allocator> FrozenVecBTreeMap<K, V, C, A> {
+    /// Returns the number of key-value pairs in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.base.len()
+    }
+
+    /// Returns `true` if the map contains no key-value pairs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.base.is_empty()
+    }
+
+    /// Returns a reference to the map's [`Comparator`].
+    #[inline]
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /// Returns a reference to the map's allocator.
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.base.allocator()
+    }
+
+    /// Returns the key-value pair at the given position in key order, or [`None`] if
+    /// `index` is out of bounds.
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        let e = &self.base[*self.order.get(index)?];
+        Some((&e.0, &e.1))
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    #[inline]
+    pub fn iter(&self) -> FrozenIter<'_, K, V> {
+        FrozenIter::new(&self.base, self.order.iter())
+    }
+
+    /// Converts the frozen map back into a [`VecBTreeMap`].
+    ///
+    /// This takes `O(n)` time and reuses the allocation.
+    #[inline]
+    #[must_use]
+    pub fn thaw(mut self) -> VecBTreeMap<K, V, C, A> {
+        permute(&mut self.base, &mut self.order);
+        VecBTreeMap {
+            base: self.base,
+            cmp: self.cmp,
+        }
+    }
+}
+
+impl<K, V, C, A> FrozenVecBTreeMap<K, V, C, A>
+where
+    C: Comparator<K>,
+    A: Allocator,
+{
+    /// Returns the position in `base` of the pair with the given key.
+    #[inline]
+    fn search<Q>(&self, k: &Q) -> Option<usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let n = self.base.len();
+        let mut i = 1;
+        while i <= n {
+            // The 16 nodes four levels down are adjacent, so fetch them while comparing.
+            prefetch(&self.base, i.wrapping_mul(16).wrapping_sub(1));
+            i = 2 * i + usize::from(k.compare(&self.base[i - 1].0, &self.cmp).is_gt());
+        }
+        // Strip the trailing right turns and the final left turn to get the
+        // last node where the search went left, i.e. the lower bound.
+        i >>= i.trailing_ones() + 1;
+        (i != 0 && k.compare(&self.base[i - 1].0, &self.cmp).is_eq()).then(|| i - 1)
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.search(k).map(|i| &self.base[i].1)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.search(k).map(|i| &mut self.base[i].1)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.search(k).map(|i| (&self.base[i].0, &self.base[i].1))
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.search(k).is_some()
+    }
+}
+
+impl<K, V, C, A: Allocator> From<VecBTreeMap<K, V, C, A>> for FrozenVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn from(map: VecBTreeMap<K, V, C, A>) -> Self {
+        let n = map.base.len();
+        let mut order = Vec::with_capacity(n);
+        in_order(1, n, &mut order);
+        let mut rank = alloc::vec![0; n];
+        for (r, &i) in order.iter().enumerate() {
+            rank[i] = r;
+        }
+        let mut base = map.base;
+        permute(&mut base, &mut rank);
+        Self {
+            base,
+            order,
+            cmp: map.cmp,
+        }
+    }
+}
+
+impl<K, V, C, A: Allocator> From<FrozenVecBTreeMap<K, V, C, A>> for VecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn from(map: FrozenVecBTreeMap<K, V, C, A>) -> Self {
+        map.thaw()
+    }
+}
+
+impl<K: Clone, V: Clone, C: Clone, A: Allocator + Clone> Clone for FrozenVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+            order: self.order.clone(),
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+impl<K: PartialEq, V: PartialEq, C, A: Allocator> PartialEq for FrozenVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base
+    }
+}
+
+impl<K: Eq, V: Eq, C, A: Allocator> Eq for FrozenVecBTreeMap<K, V, C, A> {}
+
+impl<K: Debug, V: Debug, C, A: Allocator> Debug for FrozenVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V, C, A: Allocator> IntoIterator for &'a FrozenVecBTreeMap<K, V, C, A> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = FrozenIter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the entries of a [`FrozenVecBTreeMap`], sorted by key.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct FrozenIter<'a, K, V> {
+    base: &'a [(K, V)],
+    order: slice::Iter<'a, usize>,
+}
+
+impl<'a, K, V> FrozenIter<'a, K, V> {
+    #[inline]
+    fn new(base: &'a [(K, V)], order: slice::Iter<'a, usize>) -> Self {
+        Self { base, order }
+    }
+
+    #[inline]
+    fn get(&self, i: usize) -> (&'a K, &'a V) {
+        let e = &self.base[i];
+        (&e.0, &e.1)
+    }
+}
+
+impl<K, V> Default for FrozenIter<'_, K, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(&[], [].iter())
+    }
+}
+
+impl<K, V> Clone for FrozenIter<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.base, self.order.clone())
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for FrozenIter<'_, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V> Iterator for FrozenIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.order.next().map(|&i| self.get(i))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.order.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.order.len()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.order.nth(n).map(|&i| self.get(i))
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        self.order.as_slice().last().map(|&i| self.get(i))
+    }
+}
+
+impl<K, V> DoubleEndedIterator for FrozenIter<'_, K, V> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.order.next_back().map(|&i| self.get(i))
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.order.nth_back(n).map(|&i| self.get(i))
+    }
+}
+
+impl<K, V> ExactSizeIterator for FrozenIter<'_, K, V> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.order.len()
+    }
+}
+
+impl<K, V> FusedIterator for FrozenIter<'_, K, V> {}
@@ -1,18 +1,24 @@
-use crate::VecBTreeMap;
-use core::ops::{Index, IndexMut};
+use crate::{Comparable, Comparator, VecBTreeMap};
+use allocator_api2::alloc::Allocator;
+use core::ops::Index;
 
-impl<K, V> Index<usize> for VecBTreeMap<K, V> {
+impl<K, V, C, A, Q> Index<&Q> for VecBTreeMap<K, V, C, A>
+where
+    C: Comparator<K>,
+    A: Allocator,
+    Q: Comparable<K, C> + ?Sized,
+{
     type Output = V;
 
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// Use [`get_index`](VecBTreeMap::get_index) to look up a pair by its position instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
     #[inline]
-    fn index(&self, i: usize) -> &Self::Output {
-        &self.base.index(i).1
-    }
-}
-
-impl<K, V> IndexMut<usize> for VecBTreeMap<K, V> {
-    #[inline]
-    fn index_mut(&mut self, i: usize) -> &mut Self::Output {
-        &mut self.base.index_mut(i).1
+    fn index(&self, k: &Q) -> &Self::Output {
+        self.get(k).expect("no entry found for key")
     }
 }
@@ -1,3 +1,7 @@
+#[cfg(feature = "alloc")]
+use allocator_api2::alloc::{Allocator, Global};
+#[cfg(feature = "alloc")]
+use allocator_api2::vec::{self, Vec};
 use core::fmt::{Debug, Formatter, Result};
 use core::iter::{DoubleEndedIterator, ExactSizeIterator, FusedIterator, Iterator};
 use core::slice;
@@ -167,6 +171,117 @@ impl<K, V> ExactSizeIterator for IterMut<'_, K, V> {
 
 impl<K, V> FusedIterator for IterMut<'_, K, V> {}
 
+/// An iterator over a sub-range of entries, built on the same wrapper as [`Iter`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Default)]
+pub struct Range<'a, K, V>(Iter<'a, K, V>);
+
+impl<'a, K, V> Range<'a, K, V> {
+    #[inline]
+    pub(super) fn new(base: slice::Iter<'a, (K, V)>) -> Self {
+        Self(Iter::new(base))
+    }
+}
+
+impl<K, V> Clone for Range<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for Range<'_, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.0.fmt(f)
+    }
+}
+
+/// A mutable iterator over a sub-range of entries, built on the same wrapper as [`IterMut`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Default)]
+pub struct RangeMut<'a, K, V>(IterMut<'a, K, V>);
+
+impl<'a, K, V> RangeMut<'a, K, V> {
+    #[inline]
+    pub(super) fn new(base: slice::IterMut<'a, (K, V)>) -> Self {
+        Self(IterMut::new(base))
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for RangeMut<'_, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.0.fmt(f)
+    }
+}
+
+/// Implements the iterator traits for a newtype by delegating to the wrapped iterator.
+macro_rules! delegate_iterator {
+    ($name:ident, $item:ty) => {
+        impl<'a, K, V> Iterator for $name<'a, K, V> {
+            type Item = $item;
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                self.0.next()
+            }
+
+            #[inline]
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                self.0.size_hint()
+            }
+
+            #[inline]
+            fn count(self) -> usize {
+                self.0.count()
+            }
+
+            #[inline]
+            fn nth(&mut self, n: usize) -> Option<Self::Item> {
+                self.0.nth(n)
+            }
+
+            #[inline]
+            fn last(self) -> Option<Self::Item> {
+                self.0.last()
+            }
+
+            #[inline]
+            fn fold<B, F>(self, init: B, f: F) -> B
+            where
+                F: FnMut(B, Self::Item) -> B,
+            {
+                self.0.fold(init, f)
+            }
+        }
+
+        impl<K, V> DoubleEndedIterator for $name<'_, K, V> {
+            #[inline]
+            fn next_back(&mut self) -> Option<Self::Item> {
+                self.0.next_back()
+            }
+
+            #[inline]
+            fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+                self.0.nth_back(n)
+            }
+        }
+
+        impl<K, V> ExactSizeIterator for $name<'_, K, V> {
+            #[inline]
+            fn len(&self) -> usize {
+                self.0.len()
+            }
+        }
+
+        impl<K, V> FusedIterator for $name<'_, K, V> {}
+    };
+}
+
+delegate_iterator!(Range, (&'a K, &'a V));
+delegate_iterator!(RangeMut, (&'a K, &'a mut V));
+
 #[must_use = "iterators are lazy and do nothing unless consumed"]
 #[derive(Default)]
 pub struct Keys<'a, K, V> {
@@ -416,3 +531,309 @@ impl<K, V> ExactSizeIterator for ValuesMut<'_, K, V> {
 }
 
 impl<K, V> FusedIterator for ValuesMut<'_, K, V> {}
+
+#[cfg(feature = "alloc")]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoIter<K, V, A: Allocator = Global> {
+    base: vec::IntoIter<(K, V), A>,
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator + Default> Default for IntoIter<K, V, A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Vec::new_in(A::default()).into_iter())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> IntoIter<K, V, A> {
+    #[inline]
+    pub(super) fn new(base: vec::IntoIter<(K, V), A>) -> Self {
+        Self { base }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Clone, V: Clone, A: Allocator + Clone> Clone for IntoIter<K, V, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.base.clone())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Debug, V: Debug, A: Allocator> Debug for IntoIter<K, V, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list()
+            .entries(Iter::new(self.base.as_slice().iter()))
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> Iterator for IntoIter<K, V, A> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.base.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.base.len()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.base.nth(n)
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        self.base.last()
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.base.fold(init, f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> DoubleEndedIterator for IntoIter<K, V, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.base.next_back()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.base.nth_back(n)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> ExactSizeIterator for IntoIter<K, V, A> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.base.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> FusedIterator for IntoIter<K, V, A> {}
+
+#[cfg(feature = "alloc")]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoKeys<K, V, A: Allocator = Global> {
+    base: vec::IntoIter<(K, V), A>,
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator + Default> Default for IntoKeys<K, V, A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Vec::new_in(A::default()).into_iter())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> IntoKeys<K, V, A> {
+    #[inline]
+    pub(super) fn new(base: vec::IntoIter<(K, V), A>) -> Self {
+        Self { base }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Clone, V: Clone, A: Allocator + Clone> Clone for IntoKeys<K, V, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.base.clone())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Debug, V: Debug, A: Allocator> Debug for IntoKeys<K, V, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list()
+            .entries(Keys::new(self.base.as_slice().iter()))
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> Iterator for IntoKeys<K, V, A> {
+    type Item = K;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.base.next().map(|e| e.0)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.base.len()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.base.nth(n).map(|e| e.0)
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        self.base.last().map(|e| e.0)
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.base.fold(init, |b, e| f(b, e.0))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> DoubleEndedIterator for IntoKeys<K, V, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.base.next_back().map(|e| e.0)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.base.nth_back(n).map(|e| e.0)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> ExactSizeIterator for IntoKeys<K, V, A> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.base.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> FusedIterator for IntoKeys<K, V, A> {}
+
+#[cfg(feature = "alloc")]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct IntoValues<K, V, A: Allocator = Global> {
+    base: vec::IntoIter<(K, V), A>,
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator + Default> Default for IntoValues<K, V, A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Vec::new_in(A::default()).into_iter())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> IntoValues<K, V, A> {
+    #[inline]
+    pub(super) fn new(base: vec::IntoIter<(K, V), A>) -> Self {
+        Self { base }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Clone, V: Clone, A: Allocator + Clone> Clone for IntoValues<K, V, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.base.clone())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Debug, V: Debug, A: Allocator> Debug for IntoValues<K, V, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list()
+            .entries(Values::new(self.base.as_slice().iter()))
+            .finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> Iterator for IntoValues<K, V, A> {
+    type Item = V;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.base.next().map(|e| e.1)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.base.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.base.len()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.base.nth(n).map(|e| e.1)
+    }
+
+    #[inline]
+    fn last(self) -> Option<Self::Item> {
+        self.base.last().map(|e| e.1)
+    }
+
+    #[inline]
+    fn fold<B, F>(self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        self.base.fold(init, |b, e| f(b, e.1))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> DoubleEndedIterator for IntoValues<K, V, A> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.base.next_back().map(|e| e.1)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.base.nth_back(n).map(|e| e.1)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> ExactSizeIterator for IntoValues<K, V, A> {
+    #[inline]
+    fn len(&self) -> usize {
+        self.base.len()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> FusedIterator for IntoValues<K, V, A> {}
@@ -1,32 +1,175 @@
 #![no_std]
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+mod array;
+#[cfg(feature = "alloc")]
+mod buffered;
+#[cfg(feature = "alloc")]
+mod chunked;
+mod comparator;
+#[cfg(feature = "alloc")]
+mod cursor;
+#[cfg(feature = "alloc")]
 mod deref;
+#[cfg(feature = "alloc")]
+mod entry;
+mod error;
+#[cfg(feature = "alloc")]
+mod extend;
+#[cfg(feature = "alloc")]
+mod frozen;
+#[cfg(feature = "alloc")]
 mod index;
 mod iter;
+#[cfg(feature = "alloc")]
+mod merge;
+#[cfg(feature = "rayon")]
+mod rayon;
 #[cfg(feature = "serde")]
-mod serde;
+pub mod serde;
+#[cfg(feature = "alloc")]
+mod set;
+#[cfg(feature = "alloc")]
+mod small;
+#[cfg(feature = "alloc")]
+mod soa;
 #[cfg(test)]
 mod tests;
+#[cfg(feature = "alloc")]
+mod vec;
 
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
-use core::borrow::Borrow;
+#[cfg(feature = "alloc")]
+use allocator_api2::alloc::{Allocator, Global};
+#[cfg(feature = "alloc")]
+use allocator_api2::vec::Vec as AllocVec;
+use core::cmp::Ordering;
+#[cfg(feature = "alloc")]
 use core::fmt::{self, Debug, Formatter};
+#[cfg(feature = "alloc")]
+use core::hash::{Hash, Hasher};
+#[cfg(feature = "alloc")]
 use core::mem;
+use core::ops::{self, Bound, RangeBounds};
 
-pub use iter::{Iter, IterMut, Keys, Values, ValuesMut};
+pub use array::{ArrayIntoIter, ArrayVecBTreeMap};
+#[cfg(feature = "alloc")]
+pub use buffered::{BufferedIter, BufferedVecBTreeMap};
+#[cfg(feature = "alloc")]
+pub use chunked::{ChunkedIter, ChunkedVecBTreeMap};
+pub use comparator::{Comparable, Comparator, Natural, Reverse};
+#[cfg(feature = "alloc")]
+pub use cursor::{Cursor, CursorMut};
+#[cfg(feature = "alloc")]
+pub use entry::{Entry, OccupiedEntry, VacantEntry};
+pub use error::CapacityError;
+#[cfg(feature = "alloc")]
+pub use error::{NotSorted, TryInsertError, TryReserveError};
+#[cfg(feature = "alloc")]
+pub use frozen::{FrozenIter, FrozenVecBTreeMap};
+#[cfg(feature = "alloc")]
+pub use iter::{IntoIter, IntoKeys, IntoValues};
+pub use iter::{Iter, IterMut, Keys, Range, RangeMut, Values, ValuesMut};
+#[cfg(feature = "alloc")]
+pub use merge::{Difference, Intersection, SymmetricDifference, Union};
+#[cfg(feature = "rayon")]
+pub use rayon::{IntoParIter, ParIter, ParIterMut, ParKeys, ParValues, ParValuesMut};
+#[cfg(feature = "alloc")]
+pub use set::VecBTreeSet;
+#[cfg(feature = "alloc")]
+pub use small::{SmallIntoIter, SmallVecBTreeMap};
+#[cfg(feature = "alloc")]
+pub use soa::{
+    SoaCursor, SoaCursorMut, SoaEntry, SoaOccupiedEntry, SoaVacantEntry, SoaVecBTreeMap,
+};
 
-#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct VecBTreeMap<K, V> {
-    base: Vec<(K, V)>,
+#[cfg(feature = "alloc")]
+pub struct VecBTreeMap<K, V, C = Natural, A: Allocator = Global> {
+    base: AllocVec<(K, V), A>,
+    cmp: C,
 }
 
-impl<K, V> Default for VecBTreeMap<K, V> {
+/// Returns the positions of all elements of sorted `slice` that compare equal to a key,
+/// given `order`, which compares an element to that key.
+///
+/// Unlike a binary search, this finds every match when several elements compare equal
+/// to the key, as they may for a [`Comparable`] prefix or partial key.
+fn equal_range<T, F>(slice: &[T], order: F) -> ops::Range<usize>
+where
+    F: Fn(&T) -> Ordering,
+{
+    let start = slice.partition_point(|e| order(e).is_lt());
+    let end = start + slice[start..].partition_point(|e| order(e).is_le());
+    start..end
+}
+
+/// Returns the positions of all elements of a sorted collection of length `len`
+/// whose keys lie within `range`, using `search` to find the elements equal to a key.
+///
+/// # Panics
+///
+/// Panics if the start bound lies after the end bound.
+fn range_indices<Q, R, S>(range: &R, len: usize, search: S) -> ops::Range<usize>
+where
+    Q: ?Sized,
+    R: RangeBounds<Q>,
+    S: Fn(&Q) -> ops::Range<usize>,
+{
+    let start = match range.start_bound() {
+        Bound::Included(k) => search(k).start,
+        Bound::Excluded(k) => search(k).end,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(k) => search(k).end,
+        Bound::Excluded(k) => search(k).start,
+        Bound::Unbounded => len,
+    };
+    assert!(start <= end, "range start is greater than range end");
+    start..end
+}
+
+/// Returns the position of the first element of a sorted collection that is above
+/// `bound`, or the length of the collection if there is no such element.
+#[cfg(feature = "alloc")]
+fn lower_bound_index<Q, S>(bound: Bound<&Q>, search: S) -> usize
+where
+    Q: ?Sized,
+    S: Fn(&Q) -> ops::Range<usize>,
+{
+    match bound {
+        Bound::Included(k) => search(k).start,
+        Bound::Excluded(k) => search(k).end,
+        Bound::Unbounded => 0,
+    }
+}
+
+/// Returns the position of the last element of a sorted collection of length `len`
+/// that is below `bound`, or `len` if there is no such element.
+#[cfg(feature = "alloc")]
+fn upper_bound_index<Q, S>(bound: Bound<&Q>, len: usize, search: S) -> usize
+where
+    Q: ?Sized,
+    S: Fn(&Q) -> ops::Range<usize>,
+{
+    let end = match bound {
+        Bound::Included(k) => search(k).end,
+        Bound::Excluded(k) => search(k).start,
+        Bound::Unbounded => len,
+    };
+    end.checked_sub(1).unwrap_or(len)
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, C: Default, A: Allocator + Default> Default for VecBTreeMap<K, V, C, A> {
     fn default() -> Self {
-        Self::new()
+        Self::with_comparator_in(C::default(), A::default())
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<K, V> VecBTreeMap<K, V> {
     /// Constructs a new, empty `VecBTreeMap<K, V>`.
     ///
@@ -43,7 +186,7 @@ impl<K, V> VecBTreeMap<K, V> {
     #[inline]
     #[must_use]
     pub const fn new() -> Self {
-        Self { base: Vec::new() }
+        Self::with_comparator(Natural)
     }
 
     /// Constructs a new, empty `VecBTreeMap<K, V>` with at least the specified capacity.
@@ -61,7 +204,7 @@ impl<K, V> VecBTreeMap<K, V> {
     /// always use the [`capacity`] method after construction.
     ///
     /// [Capacity and reallocation]: Vec#capacity-and-reallocation
-    /// [`capacity`]: Vec::capacity
+    /// [`capacity`]: VecBTreeMap::capacity
     ///
     /// # Panics
     ///
@@ -94,211 +237,300 @@ impl<K, V> VecBTreeMap<K, V> {
     #[inline]
     #[must_use]
     pub fn with_capacity(capacity: usize) -> Self {
-        Self {
-            base: Vec::with_capacity(capacity),
-        }
+        Self::with_capacity_and_comparator(capacity, Natural)
     }
 
-    /// An iterator yielding all key-value paris from start to end.
-    /// The iterator element type is `(&K, &V)`.
+    /// Constructs a new, empty `VecBTreeMap<K, V>` with at least the specified capacity,
+    /// returning an error instead of panicking if the allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError`] if the capacity overflows or the allocator reports a failure.
     ///
     /// # Examples
     ///
     /// ```
     /// use vec_btree_map::VecBTreeMap;
     ///
-    /// let mut map = VecBTreeMap::with_capacity(3);
-    /// map.insert("a", 1);
-    /// map.insert("b", 2);
-    /// map.insert("c", 3);
-    ///
-    /// let mut iter = map.iter();
+    /// let map: VecBTreeMap<i32, i32> = VecBTreeMap::try_with_capacity(10).unwrap();
+    /// assert!(map.capacity() >= 10);
     ///
-    /// for (key, value) in iter {
-    ///     println!("{key}: {value}");
-    /// }
+    /// assert!(VecBTreeMap::<u64, u64>::try_with_capacity(usize::MAX).is_err());
     /// ```
     #[inline]
-    pub fn iter(&self) -> Iter<'_, K, V> {
-        Iter::new(self.base.iter())
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_with_capacity_in(capacity, Global)
     }
 
-    /// An iterator yielding all key-value pairs from start to end, with mutable references to the values.
-    /// The iterator element type is (&K, &mut V).
+    /// Constructs a `VecBTreeMap<K, V>` from a vec without checking that its keys are sorted.
+    ///
+    /// The keys of `base` must be strictly increasing, see
+    /// [`try_from_sorted_vec`](Self::try_from_sorted_vec) for a checked version.
+    /// If they are not, the behavior of the map is unspecified, but memory safe.
+    /// Only debug builds verify the order.
     ///
     /// # Examples
     ///
     /// ```
     /// use vec_btree_map::VecBTreeMap;
     ///
-    /// let mut map = VecBTreeMap::with_capacity(3);
-    /// map.insert("a", 1);
-    /// map.insert("b", 2);
-    /// map.insert("c", 3);
-    ///
-    /// // Update all values
-    /// for (_, value) in map.iter_mut() {
-    ///     *value *= 2;
-    /// }
-    ///
-    /// for (key, value) in map.iter() {
-    ///     println!("{key}: {value}");
-    /// }
+    /// let map = VecBTreeMap::from_sorted_vec_unchecked(vec![("a", 1), ("b", 2)]);
+    /// assert_eq!(map.get("b"), Some(&2));
     /// ```
     #[inline]
-    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
-        IterMut::new(self.base.iter_mut())
+    #[must_use]
+    pub fn from_sorted_vec_unchecked(base: Vec<(K, V)>) -> Self
+    where
+        K: Ord,
+    {
+        debug_assert!(base.is_sorted_by(|a, b| a.0 < b.0));
+        Self {
+            base: vec::from_std(base),
+            cmp: Natural,
+        }
     }
 
-    /// An iterator yielding all keys from start to end.
-    /// The iterator element type is `&K`.
+    /// Constructs a `VecBTreeMap<K, V>` from a vec whose keys are already strictly increasing.
+    ///
+    /// The order is validated in a single pass, and the vec is taken over without copying.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotSorted`] holding the unchanged vec and the position of the
+    /// first key that is not greater than its predecessor.
     ///
     /// # Examples
     ///
     /// ```
     /// use vec_btree_map::VecBTreeMap;
     ///
-    /// let mut map = VecBTreeMap::with_capacity(3);
-    /// map.insert("a", 1);
-    /// map.insert("b", 2);
-    /// map.insert("c", 3);
-    ///
-    /// let mut keys = map.keys();
+    /// let map = VecBTreeMap::try_from_sorted_vec(vec![(1, "a"), (2, "b")]).unwrap();
+    /// assert_eq!(map.get(&2), Some(&"b"));
     ///
-    /// assert_eq!(keys.next(), Some(&"a"));
-    /// assert_eq!(keys.next(), Some(&"b"));
-    /// assert_eq!(keys.next(), Some(&"c"));
-    /// assert_eq!(keys.next(), None);
+    /// let err = VecBTreeMap::try_from_sorted_vec(vec![(1, "a"), (3, "c"), (2, "b")]).unwrap_err();
+    /// assert_eq!(err.index(), 2);
+    /// assert_eq!(err.into_vec(), [(1, "a"), (3, "c"), (2, "b")]);
     /// ```
     #[inline]
-    pub fn keys(&self) -> Keys<'_, K, V> {
-        Keys::new(self.base.iter())
+    pub fn try_from_sorted_vec(base: Vec<(K, V)>) -> Result<Self, NotSorted<K, V>>
+    where
+        K: Ord,
+    {
+        Self::try_from_sorted_vec_and_comparator(base, Natural)
     }
+}
 
-    /// An iterator yielding all values from start to end.
-    /// The iterator element type is `&V`.
+#[cfg(feature = "alloc")]
+impl<K, V, A: Allocator> VecBTreeMap<K, V, Natural, A> {
+    /// Constructs a new, empty `VecBTreeMap<K, V, Natural, A>` which allocates in `alloc`.
+    ///
+    /// The map will not allocate until it is first inserted into.
     ///
     /// # Examples
     ///
     /// ```
+    /// # #![allow(unused_mut)]
+    /// use allocator_api2::alloc::Global;
     /// use vec_btree_map::VecBTreeMap;
     ///
-    /// let mut map = VecBTreeMap::with_capacity(3);
-    /// map.insert("a", 1);
-    /// map.insert("b", 2);
-    /// map.insert("c", 3);
+    /// let mut map: VecBTreeMap<String, f64> = VecBTreeMap::new_in(Global);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn new_in(alloc: A) -> Self {
+        Self::with_comparator_in(Natural, alloc)
+    }
+
+    /// Constructs a new, empty `VecBTreeMap<K, V, Natural, A>` with at least the specified
+    /// capacity, which allocates in `alloc`.
     ///
-    ///let mut keys = map.values();
+    /// See [`with_capacity`](VecBTreeMap::with_capacity) for details about the capacity.
     ///
-    /// assert_eq!(keys.next(), Some(&1));
-    /// assert_eq!(keys.next(), Some(&2));
-    /// assert_eq!(keys.next(), Some(&3));
-    /// assert_eq!(keys.next(), None);
-    /// ```
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
     #[inline]
-    pub fn values(&self) -> Values<'_, K, V> {
-        Values::new(self.base.iter())
+    #[must_use]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self::with_capacity_and_comparator_in(capacity, Natural, alloc)
     }
 
-    /// An iterator yielding all values mutably from start to end.
-    /// The iterator element type is `&V`.
+    /// Constructs a new, empty `VecBTreeMap<K, V, Natural, A>` with at least the specified
+    /// capacity, which allocates in `alloc`, returning an error if the allocation fails.
     ///
-    /// # Examples
+    /// # Errors
     ///
-    /// ```
-    /// use vec_btree_map::VecBTreeMap;
+    /// Returns [`TryReserveError`] if the capacity overflows or the allocator reports a failure.
+    #[inline]
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        let mut map = Self::new_in(alloc);
+        map.try_reserve(capacity)?;
+        Ok(map)
+    }
+
+    /// Constructs a `VecBTreeMap<K, V, Natural, A>` from a vec whose keys are already
+    /// strictly increasing, keeping the vec's allocator.
     ///
-    /// let mut map = VecBTreeMap::with_capacity(3);
-    /// map.insert("a", 1);
-    /// map.insert("b", 2);
-    /// map.insert("c", 3);
+    /// See [`try_from_sorted_vec`](VecBTreeMap::try_from_sorted_vec) for details.
     ///
-    /// for val in map.values_mut() {
-    ///     *val *= *val;
-    /// }
+    /// # Errors
     ///
-    ///let mut keys = map.values();
+    /// Returns [`NotSorted`] holding the unchanged vec and the position of the
+    /// first key that is not greater than its predecessor.
     ///
-    /// assert_eq!(keys.next(), Some(&1));
-    /// assert_eq!(keys.next(), Some(&4));
-    /// assert_eq!(keys.next(), Some(&9));
-    /// assert_eq!(keys.next(), None);
+    /// # Examples
+    ///
+    /// ```
+    /// use allocator_api2::alloc::Global;
+    /// use allocator_api2::vec::Vec;
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut base = Vec::new_in(Global);
+    /// base.extend([(1, "a"), (2, "b")]);
+    /// let map = VecBTreeMap::try_from_sorted_vec_in(base).unwrap();
+    /// assert_eq!(map.get(&2), Some(&"b"));
     /// ```
     #[inline]
-    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
-        ValuesMut::new(self.base.iter_mut())
+    pub fn try_from_sorted_vec_in(base: AllocVec<(K, V), A>) -> Result<Self, NotSorted<K, V, A>>
+    where
+        K: Ord,
+    {
+        Self::try_from_sorted_vec_and_comparator_in(base, Natural)
     }
 }
 
-impl<K, V> VecBTreeMap<K, V>
-where
-    K: Ord,
-{
-    /// Binary searches this map for a given key.
+#[cfg(feature = "alloc")]
+impl<K, V, C> VecBTreeMap<K, V, C> {
+    /// Constructs a new, empty `VecBTreeMap<K, V, C>` which orders its keys with `cmp`.
     ///
-    /// If the key is found then [`Result::Ok`] is returned, containing the
-    /// index of the matching key.
-    /// If the key is not found then [`Result::Err`] is returned, containing
-    /// the index where a matching key-value pair could be inserted while maintaining
-    /// sorted order.
+    /// The map is initially created with a capacity of 0, so it will not allocate until it is first inserted into.
     ///
     /// # Examples
     ///
-    /// Looks up a series of four elements.
-    /// The first is found, the second and third are not.
-    ///
     /// ```
-    /// use vec_btree_map::VecBTreeMap;
+    /// use vec_btree_map::{Natural, Reverse, VecBTreeMap};
     ///
-    /// let mut map = VecBTreeMap::with_capacity(3);
+    /// let mut map = VecBTreeMap::with_comparator(Reverse(Natural));
     /// map.insert("a", 1);
-    /// map.insert("c", 2);
-    /// map.insert("d", 3);
+    /// map.insert("b", 2);
     ///
-    /// assert_eq!(map.binary_search("a"), Ok(0));
-    /// assert_eq!(map.binary_search("b"), Err(1));
-    /// assert_eq!(map.binary_search("e"), Err(3));
+    /// assert_eq!(map.keys().collect::<Vec<_>>(), [&"b", &"a"]);
     /// ```
     #[inline]
-    pub fn binary_search<Q>(&self, k: &Q) -> Result<usize, usize>
+    #[must_use]
+    pub const fn with_comparator(cmp: C) -> Self {
+        Self::with_comparator_in(cmp, Global)
+    }
+
+    /// Constructs a new, empty `VecBTreeMap<K, V, C>` with at least the specified capacity,
+    /// which orders its keys with `cmp`.
+    ///
+    /// See [`with_capacity`](VecBTreeMap::with_capacity) for details about the capacity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_and_comparator(capacity: usize, cmp: C) -> Self {
+        Self::with_capacity_and_comparator_in(capacity, cmp, Global)
+    }
+
+    /// Constructs a `VecBTreeMap<K, V, C>` from a vec whose keys are already strictly
+    /// increasing according to `cmp`.
+    ///
+    /// See [`try_from_sorted_vec`](VecBTreeMap::try_from_sorted_vec) for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotSorted`] holding the unchanged vec and the position of the
+    /// first key that is not greater than its predecessor.
+    #[inline]
+    pub fn try_from_sorted_vec_and_comparator(
+        base: Vec<(K, V)>,
+        cmp: C,
+    ) -> Result<Self, NotSorted<K, V>>
     where
-        Q: Ord + ?Sized,
-        K: Borrow<Q>,
+        C: Comparator<K>,
     {
-        self.base.binary_search_by(|e| e.0.borrow().cmp(k))
+        Self::try_from_sorted_vec_and_comparator_in(vec::from_std(base), cmp)
     }
+}
 
-    /// Appends a key-value pair to the back of the map.
+#[cfg(feature = "alloc")]
+impl<K, V, C, A: Allocator> VecBTreeMap<K, V, C, A> {
+    /// Constructs a new, empty `VecBTreeMap<K, V, C, A>` which orders its keys with `cmp`
+    /// and allocates in `alloc`.
     ///
-    /// If the map woudn't be sorted anymore by appending
-    /// the key-value pair to the back of the map, [`Some`]`(K, V)` is returned.
-    /// Otherwise [`None`] is returned.
+    /// The map will not allocate until it is first inserted into.
+    #[inline]
+    #[must_use]
+    pub const fn with_comparator_in(cmp: C, alloc: A) -> Self {
+        Self {
+            base: AllocVec::new_in(alloc),
+            cmp,
+        }
+    }
+
+    /// Constructs a new, empty `VecBTreeMap<K, V, C, A>` with at least the specified capacity,
+    /// which orders its keys with `cmp` and allocates in `alloc`.
+    ///
+    /// See [`with_capacity`](VecBTreeMap::with_capacity) for details about the capacity.
     ///
     /// # Panics
     ///
     /// Panics if the new capacity exceeds `isize::MAX` bytes.
     #[inline]
-    pub fn push(&mut self, k: K, v: V) -> Option<(K, V)> {
-        let last = self.len().saturating_sub(1);
-        if let Some((key, _)) = self.get(last)
-            && key >= &k
-        {
-            return Some((k, v));
+    #[must_use]
+    pub fn with_capacity_and_comparator_in(capacity: usize, cmp: C, alloc: A) -> Self {
+        Self {
+            base: AllocVec::with_capacity_in(capacity, alloc),
+            cmp,
         }
-        self.base.push((k, v));
-        None
     }
 
-    /// Inserts a key-value pair into the map.
+    /// Returns a reference to the map's [`Comparator`].
+    #[inline]
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /// Returns a reference to the map's allocator.
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.base.allocator()
+    }
+
+    /// Constructs a `VecBTreeMap<K, V, C, A>` from a vec whose keys are already strictly
+    /// increasing according to `cmp`, keeping the vec's allocator.
     ///
-    /// If the map did not have this key present, [`None`] is returned.
+    /// See [`try_from_sorted_vec`](VecBTreeMap::try_from_sorted_vec) for details.
     ///
-    /// If the map did have this key present, the value is updated, and the old
-    /// value is returned. The key is not updated.
+    /// # Errors
     ///
-    /// # Panics
+    /// Returns [`NotSorted`] holding the unchanged vec and the position of the
+    /// first key that is not greater than its predecessor.
+    #[inline]
+    pub fn try_from_sorted_vec_and_comparator_in(
+        base: AllocVec<(K, V), A>,
+        cmp: C,
+    ) -> Result<Self, NotSorted<K, V, A>>
+    where
+        C: Comparator<K>,
+    {
+        match base
+            .windows(2)
+            .position(|w| cmp.compare(&w[0].0, &w[1].0).is_ge())
+        {
+            Some(i) => Err(NotSorted::new(base, i + 1)),
+            None => Ok(Self { base, cmp }),
+        }
+    }
+
+    /// Consumes the map and returns the underlying vec of key-value pairs, sorted by key.
     ///
-    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    /// This does not copy or reallocate, and the vec keeps the map's allocator. Maps in
+    /// the global allocator can also be converted into a [`Vec`] with [`From`].
     ///
     /// # Examples
     ///
@@ -306,54 +538,50 @@ where
     /// use vec_btree_map::VecBTreeMap;
     ///
     /// let mut map = VecBTreeMap::new();
-    ///
-    /// assert_eq!(map.is_empty(), true);
-    /// assert_eq!(map.insert("a", 1), None);
-    /// assert_eq!(map.is_empty(), false);
-    ///
-    /// map.insert("a", 2);
-    /// assert_eq!(map.insert("a", 3), Some(2));
-    /// assert_eq!(map[0], 3);
+    /// map.insert("b", 2);
+    /// map.insert("a", 1);
+    /// assert_eq!(map.clone().into_vec(), [("a", 1), ("b", 2)]);
+    /// assert_eq!(Vec::from(map), [("a", 1), ("b", 2)]);
     /// ```
     #[inline]
-    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
-        match self.binary_search(&k) {
-            Ok(i) => Some(mem::replace(&mut self.base[i].1, v)),
-            Err(i) => {
-                self.base.insert(i, (k, v));
-                None
-            }
-        }
+    #[must_use]
+    pub fn into_vec(self) -> AllocVec<(K, V), A> {
+        self.base
     }
 
-    /// Removes a key from the map, returning the value at the key if the key
-    /// was previously in the map.
+    /// Converts the map into a [`FrozenVecBTreeMap`], which is faster to search
+    /// but can no longer be modified.
     ///
-    /// The key may be any borrowed form of the map's key type, but
-    /// [`Ord`] on the borrowed form *must* match those for
-    /// the key type.
+    /// This takes `O(n)` time. The pairs are rearranged in place, but an index of
+    /// their positions is allocated next to them in the global allocator.
+    #[inline]
+    #[must_use]
+    pub fn freeze(self) -> FrozenVecBTreeMap<K, V, C, A> {
+        self.into()
+    }
+
+    /// Returns the number of key-value pairs the map can hold without reallocating.
     ///
     /// # Examples
     ///
     /// ```
     /// use vec_btree_map::VecBTreeMap;
     ///
-    /// let mut map = VecBTreeMap::new();
-    /// map.insert("a", 1);
-    /// assert_eq!(map.remove("a"), Some(1));
-    /// assert_eq!(map.remove("a"), None);
+    /// let map: VecBTreeMap<i32, i32> = VecBTreeMap::with_capacity(10);
+    /// assert!(map.capacity() >= 10);
     /// ```
     #[inline]
-    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
-    where
-        Q: Ord + ?Sized,
-        K: Borrow<Q>,
-    {
-        self.binary_search(k).map(|i| self.base.remove(i).1).ok()
+    pub fn capacity(&self) -> usize {
+        self.base.capacity()
     }
 
-    /// Removes the last key-value pair from the map and returns it, or [`None`] if it
-    /// is empty.
+    /// Reserves capacity for at least `additional` more key-value pairs.
+    /// The map may reserve more space to speculatively avoid frequent reallocations.
+    /// Does nothing if the capacity is already sufficient.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
     ///
     /// # Examples
     ///
@@ -362,20 +590,1075 @@ where
     ///
     /// let mut map = VecBTreeMap::new();
     /// map.insert("a", 1);
-    /// assert_eq!(map.pop(), Some(("a", 1)));
-    /// assert_eq!(map.pop(), None);
+    /// map.reserve(10);
+    /// assert!(map.capacity() >= 11);
     /// ```
     #[inline]
-    pub fn pop(&mut self) -> Option<(K, V)> {
-        self.base.pop()
+    pub fn reserve(&mut self, additional: usize) {
+        self.base.reserve(additional);
     }
 
-    /// Clears the map, removing all key-value pairs. Keeps the allocated memory
-    /// for reuse.
+    /// Reserves the minimum capacity for exactly `additional` more key-value pairs.
+    /// Does nothing if the capacity is already sufficient.
     ///
-    /// # Examples
+    /// Prefer [`reserve`](Self::reserve) if future insertions are expected.
     ///
-    /// ```
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.base.reserve_exact(additional);
+    }
+
+    /// Tries to reserve capacity for at least `additional` more key-value pairs.
+    /// The map may reserve more space to speculatively avoid frequent reallocations.
+    /// Does nothing if the capacity is already sufficient.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError`] if the capacity overflows or the allocator reports a
+    /// failure. The map is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map: VecBTreeMap<u64, u64> = VecBTreeMap::new();
+    /// map.try_reserve(10).unwrap();
+    /// assert!(map.capacity() >= 10);
+    ///
+    /// assert!(map.try_reserve(usize::MAX).is_err());
+    /// ```
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.base.try_reserve(additional)
+    }
+
+    /// Tries to reserve the minimum capacity for exactly `additional` more key-value pairs.
+    /// Does nothing if the capacity is already sufficient.
+    ///
+    /// Prefer [`try_reserve`](Self::try_reserve) if future insertions are expected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError`] if the capacity overflows or the allocator reports a
+    /// failure. The map is left unchanged.
+    #[inline]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.base.try_reserve_exact(additional)
+    }
+
+    /// Shrinks the capacity of the map as much as possible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::with_capacity(10);
+    /// map.insert("a", 1);
+    /// map.shrink_to_fit();
+    /// assert!(map.capacity() >= 1);
+    /// ```
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.base.shrink_to_fit();
+    }
+
+    /// Shrinks the capacity of the map with a lower limit.
+    ///
+    /// The capacity will remain at least as large as both the length and the supplied value.
+    /// Does nothing if the current capacity is less than the lower limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::with_capacity(10);
+    /// map.insert("a", 1);
+    /// map.shrink_to(4);
+    /// assert!(map.capacity() >= 4);
+    /// map.shrink_to(0);
+    /// assert!(map.capacity() >= 1);
+    /// ```
+    #[inline]
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.base.shrink_to(min_capacity);
+    }
+
+    /// Splits the map into two at the given position.
+    ///
+    /// Returns a newly allocated map containing the key-value pairs in the range `[at, len)`.
+    /// After the call, the original map will be left containing the pairs `[0, at)`
+    /// with its previous capacity unchanged.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut a: VecBTreeMap<_, _> = [(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+    /// let b = a.split_off_index(1);
+    ///
+    /// assert_eq!(a.into_vec(), [(1, "a")]);
+    /// assert_eq!(b.into_vec(), [(2, "b"), (3, "c")]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn split_off_index(&mut self, at: usize) -> Self
+    where
+        C: Clone,
+        A: Clone,
+    {
+        Self {
+            base: self.base.split_off(at),
+            cmp: self.cmp.clone(),
+        }
+    }
+
+    /// An iterator yielding all key-value paris from start to end.
+    /// The iterator element type is `(&K, &V)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::with_capacity(3);
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
+    ///
+    /// let mut iter = map.iter();
+    ///
+    /// for (key, value) in iter {
+    ///     println!("{key}: {value}");
+    /// }
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.base.iter())
+    }
+
+    /// An iterator yielding all key-value pairs from start to end, with mutable references to the values.
+    /// The iterator element type is (&K, &mut V).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::with_capacity(3);
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
+    ///
+    /// // Update all values
+    /// for (_, value) in map.iter_mut() {
+    ///     *value *= 2;
+    /// }
+    ///
+    /// for (key, value) in map.iter() {
+    ///     println!("{key}: {value}");
+    /// }
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(self.base.iter_mut())
+    }
+
+    /// An iterator yielding all keys from start to end.
+    /// The iterator element type is `&K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::with_capacity(3);
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
+    ///
+    /// let mut keys = map.keys();
+    ///
+    /// assert_eq!(keys.next(), Some(&"a"));
+    /// assert_eq!(keys.next(), Some(&"b"));
+    /// assert_eq!(keys.next(), Some(&"c"));
+    /// assert_eq!(keys.next(), None);
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(self.base.iter())
+    }
+
+    /// An iterator yielding all values from start to end.
+    /// The iterator element type is `&V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::with_capacity(3);
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
+    ///
+    ///let mut keys = map.values();
+    ///
+    /// assert_eq!(keys.next(), Some(&1));
+    /// assert_eq!(keys.next(), Some(&2));
+    /// assert_eq!(keys.next(), Some(&3));
+    /// assert_eq!(keys.next(), None);
+    /// ```
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values::new(self.base.iter())
+    }
+
+    /// An iterator yielding all values mutably from start to end.
+    /// The iterator element type is `&V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::with_capacity(3);
+    /// map.insert("a", 1);
+    /// map.insert("b", 2);
+    /// map.insert("c", 3);
+    ///
+    /// for val in map.values_mut() {
+    ///     *val *= *val;
+    /// }
+    ///
+    ///let mut keys = map.values();
+    ///
+    /// assert_eq!(keys.next(), Some(&1));
+    /// assert_eq!(keys.next(), Some(&4));
+    /// assert_eq!(keys.next(), Some(&9));
+    /// assert_eq!(keys.next(), None);
+    /// ```
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(self.base.iter_mut())
+    }
+
+    /// Creates a consuming iterator visiting all the keys, in sorted order.
+    /// The map cannot be used after calling this.
+    /// The iterator element type is `K`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert(2, "b");
+    /// map.insert(1, "a");
+    ///
+    /// let keys: Vec<i32> = map.into_keys().collect();
+    /// assert_eq!(keys, [1, 2]);
+    /// ```
+    #[inline]
+    pub fn into_keys(self) -> IntoKeys<K, V, A> {
+        IntoKeys::new(self.base.into_iter())
+    }
+
+    /// Creates a consuming iterator visiting all the values, in order by key.
+    /// The map cannot be used after calling this.
+    /// The iterator element type is `V`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert(1, "hello");
+    /// map.insert(2, "goodbye");
+    ///
+    /// let values: Vec<&str> = map.into_values().collect();
+    /// assert_eq!(values, ["hello", "goodbye"]);
+    /// ```
+    #[inline]
+    pub fn into_values(self) -> IntoValues<K, V, A> {
+        IntoValues::new(self.base.into_iter())
+    }
+
+    /// Returns the key-value pair at the given position, or [`None`] if
+    /// `index` is out of bounds.
+    ///
+    /// This is a positional lookup, use [`get`](Self::get) to look up a value by its key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert("b", 2);
+    /// map.insert("a", 1);
+    ///
+    /// assert_eq!(map.get_index(0), Some((&"a", &1)));
+    /// assert_eq!(map.get_index(1), Some((&"b", &2)));
+    /// assert_eq!(map.get_index(2), None);
+    /// ```
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.base.get(index).map(|e| (&e.0, &e.1))
+    }
+
+    /// Returns the key and a mutable reference to the value at the given position,
+    /// or [`None`] if `index` is out of bounds.
+    ///
+    /// This is a positional lookup, use [`get_mut`](Self::get_mut) to look up a value by its key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert("a", 1);
+    ///
+    /// if let Some((_, v)) = map.get_index_mut(0) {
+    ///     *v = 10;
+    /// }
+    /// assert_eq!(map.get_index(0), Some((&"a", &10)));
+    /// ```
+    #[inline]
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        self.base.get_mut(index).map(|e| (&e.0, &mut e.1))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, C, A> VecBTreeMap<K, V, C, A>
+where
+    C: Comparator<K>,
+    A: Allocator,
+{
+    /// Binary searches this map for a given key.
+    ///
+    /// If the key is found then [`Result::Ok`] is returned, containing the
+    /// index of the matching key.
+    /// If the key is not found then [`Result::Err`] is returned, containing
+    /// the index where a matching key-value pair could be inserted while maintaining
+    /// sorted order.
+    ///
+    /// # Examples
+    ///
+    /// Looks up a series of four elements.
+    /// The first is found, the second and third are not.
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::with_capacity(3);
+    /// map.insert("a", 1);
+    /// map.insert("c", 2);
+    /// map.insert("d", 3);
+    ///
+    /// assert_eq!(map.binary_search("a"), Ok(0));
+    /// assert_eq!(map.binary_search("b"), Err(1));
+    /// assert_eq!(map.binary_search("e"), Err(3));
+    /// ```
+    #[inline]
+    pub fn binary_search<Q>(&self, k: &Q) -> Result<usize, usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.base
+            .binary_search_by(|e| k.compare(&e.0, &self.cmp).reverse())
+    }
+
+    /// Returns the positions of all keys that compare equal to `k`.
+    #[inline]
+    fn equal_range<Q>(&self, k: &Q) -> ops::Range<usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        equal_range(&self.base, |e| k.compare(&e.0, &self.cmp).reverse())
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other
+    /// [`Comparable`] type, but the ordering *must* match the ordering of
+    /// the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.get(&1), Some(&"a"));
+    /// assert_eq!(map.get(&2), None);
+    /// ```
+    #[inline]
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.binary_search(k).ok().map(|i| &self.base[i].1)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other
+    /// [`Comparable`] type, but the ordering *must* match the ordering of
+    /// the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert(1, "a");
+    /// if let Some(x) = map.get_mut(&1) {
+    ///     *x = "b";
+    /// }
+    /// assert_eq!(map[&1], "b");
+    /// ```
+    #[inline]
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.binary_search(k).ok().map(|i| &mut self.base[i].1)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// The supplied key may be any borrowed form of the map's key type, or any other
+    /// [`Comparable`] type, but the ordering *must* match the ordering of
+    /// the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.get_key_value(&1), Some((&1, &"a")));
+    /// assert_eq!(map.get_key_value(&2), None);
+    /// ```
+    #[inline]
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.binary_search(k).ok().and_then(|i| self.get_index(i))
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other
+    /// [`Comparable`] type, but the ordering *must* match the ordering of
+    /// the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert(1, "a");
+    /// assert_eq!(map.contains_key(&1), true);
+    /// assert_eq!(map.contains_key(&2), false);
+    /// ```
+    #[inline]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.binary_search(k).is_ok()
+    }
+
+    /// Appends a key-value pair to the back of the map.
+    ///
+    /// If the map woudn't be sorted anymore by appending
+    /// the key-value pair to the back of the map, [`Some`]`(K, V)` is returned.
+    /// Otherwise [`None`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn push(&mut self, k: K, v: V) -> Option<(K, V)> {
+        if let Some((key, _)) = self.base.last()
+            && self.cmp.compare(key, &k).is_ge()
+        {
+            return Some((k, v));
+        }
+        self.base.push((k, v));
+        None
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, [`None`] is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned. The key is not updated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    ///
+    /// assert_eq!(map.is_empty(), true);
+    /// assert_eq!(map.insert("a", 1), None);
+    /// assert_eq!(map.is_empty(), false);
+    ///
+    /// map.insert("a", 2);
+    /// assert_eq!(map.insert("a", 3), Some(2));
+    /// assert_eq!(map["a"], 3);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        match self.binary_search(&k) {
+            Ok(i) => Some(mem::replace(&mut self.base[i].1, v)),
+            Err(i) => {
+                self.base.insert(i, (k, v));
+                None
+            }
+        }
+    }
+
+    /// Inserts a key-value pair into the map, returning an error instead of panicking
+    /// if the map needs to grow and the allocation fails.
+    ///
+    /// Otherwise this behaves like [`insert`](Self::insert). Updating the value of a
+    /// key that is already present never allocates.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryInsertError`] holding the rejected key-value pair if the capacity
+    /// overflows or the allocator reports a failure. The map is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    ///
+    /// assert_eq!(map.try_insert("a", 1), Ok(None));
+    /// assert_eq!(map.try_insert("a", 2), Ok(Some(1)));
+    /// assert_eq!(map["a"], 2);
+    /// ```
+    #[inline]
+    pub fn try_insert(&mut self, k: K, v: V) -> Result<Option<V>, TryInsertError<K, V>> {
+        match self.binary_search(&k) {
+            Ok(i) => Ok(Some(mem::replace(&mut self.base[i].1, v))),
+            Err(i) => match self.base.try_reserve(1) {
+                Ok(()) => {
+                    self.base.insert(i, (k, v));
+                    Ok(None)
+                }
+                Err(e) => Err(TryInsertError::new((k, v), e)),
+            },
+        }
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of key-value pairs in the map.
+    /// The simplest way is to use the range syntax `min..max`, thus `range(min..max)` will
+    /// yield elements from min (inclusive) to max (exclusive).
+    /// The range may also be entered as `(Bound<T>, Bound<T>)`, so for example
+    /// `range((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
+    /// range from 4 to 10.
+    /// Since the bounds may be of any [`Comparable`] type, a fully unbounded range
+    /// needs its type spelled out, e.g. `range::<K, _>(..)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end` and the map contains a key within `end..=start`.
+    /// Other inverted ranges are empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound::Included;
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert(3, "a");
+    /// map.insert(5, "b");
+    /// map.insert(8, "c");
+    ///
+    /// for (key, value) in map.range((Included(4), Included(8))) {
+    ///     println!("{key}: {value}");
+    /// }
+    /// assert_eq!(Some((&5, &"b")), map.range(4..).next());
+    /// ```
+    #[inline]
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let indices = range_indices(&range, self.base.len(), |k| self.equal_range(k));
+        Range::new(self.base[indices].iter())
+    }
+
+    /// Constructs a mutable double-ended iterator over a sub-range of key-value pairs in the map.
+    /// The simplest way is to use the range syntax `min..max`, thus `range_mut(min..max)` will
+    /// yield elements from min (inclusive) to max (exclusive).
+    /// The range may also be entered as `(Bound<T>, Bound<T>)`, so for example
+    /// `range_mut((Excluded(4), Included(10)))` will yield a left-exclusive, right-inclusive
+    /// range from 4 to 10.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end` and the map contains a key within `end..=start`.
+    /// Other inverted ranges are empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert("Alice", 0);
+    /// map.insert("Bob", 0);
+    /// map.insert("Carol", 0);
+    /// map.insert("Cheryl", 0);
+    ///
+    /// for (_, balance) in map.range_mut("B".."Cheryl") {
+    ///     *balance += 100;
+    /// }
+    ///
+    /// for (name, balance) in map.iter() {
+    ///     println!("{name} => {balance}");
+    /// }
+    /// assert_eq!(map.get("Bob"), Some(&100));
+    /// assert_eq!(map.get("Cheryl"), Some(&0));
+    /// ```
+    #[inline]
+    pub fn range_mut<Q, R>(&mut self, range: R) -> RangeMut<'_, K, V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let indices = range_indices(&range, self.base.len(), |k| self.equal_range(k));
+        RangeMut::new(self.base[indices].iter_mut())
+    }
+
+    /// Visits the key-value pairs representing the union,
+    /// i.e., all the keys in `self` or `other`, without duplicates,
+    /// in ascending order. If a key is in both maps, the value of `self` is yielded.
+    ///
+    /// The maps are merged lazily in `O(n + m)`. Since the keys come out sorted,
+    /// collecting them into a new [`VecBTreeMap`] does not sort again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let a: VecBTreeMap<_, _> = [(1, "a"), (2, "b")].into_iter().collect();
+    /// let b: VecBTreeMap<_, _> = [(2, "B"), (3, "C")].into_iter().collect();
+    ///
+    /// let union: VecBTreeMap<_, _> = a.union(&b).collect();
+    /// assert_eq!(union.into_vec(), [(&1, &"a"), (&2, &"b"), (&3, &"C")]);
+    /// ```
+    #[inline]
+    pub fn union<'a>(&'a self, other: &'a Self) -> Union<'a, K, V, C> {
+        Union::new(&self.base, &other.base, &self.cmp)
+    }
+
+    /// Visits the keys representing the intersection,
+    /// i.e., the keys that are both in `self` and `other`,
+    /// in ascending order, together with the value of `self` and of `other`.
+    ///
+    /// The maps are merged lazily in `O(n + m)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let a: VecBTreeMap<_, _> = [(1, 10), (2, 20)].into_iter().collect();
+    /// let b: VecBTreeMap<_, _> = [(2, 2), (3, 3)].into_iter().collect();
+    ///
+    /// let product: VecBTreeMap<_, _> = a.intersection(&b).map(|(k, x, y)| (*k, x * y)).collect();
+    /// assert_eq!(product.into_vec(), [(2, 40)]);
+    /// ```
+    #[inline]
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> Intersection<'a, K, V, C> {
+        Intersection::new(&self.base, &other.base, &self.cmp)
+    }
+
+    /// Visits the key-value pairs representing the difference,
+    /// i.e., the keys that are in `self` but not in `other`,
+    /// in ascending order.
+    ///
+    /// The maps are merged lazily in `O(n + m)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let a: VecBTreeMap<_, _> = [(1, "a"), (2, "b")].into_iter().collect();
+    /// let b: VecBTreeMap<_, _> = [(2, "B"), (3, "C")].into_iter().collect();
+    ///
+    /// let diff: Vec<_> = a.difference(&b).collect();
+    /// assert_eq!(diff, [(&1, &"a")]);
+    /// ```
+    #[inline]
+    pub fn difference<'a>(&'a self, other: &'a Self) -> Difference<'a, K, V, C> {
+        Difference::new(&self.base, &other.base, &self.cmp)
+    }
+
+    /// Visits the key-value pairs representing the symmetric difference,
+    /// i.e., the keys that are in `self` or in `other` but not in both,
+    /// in ascending order.
+    ///
+    /// The maps are merged lazily in `O(n + m)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let a: VecBTreeMap<_, _> = [(1, "a"), (2, "b")].into_iter().collect();
+    /// let b: VecBTreeMap<_, _> = [(2, "B"), (3, "C")].into_iter().collect();
+    ///
+    /// let sym_diff: Vec<_> = a.symmetric_difference(&b).collect();
+    /// assert_eq!(sym_diff, [(&1, &"a"), (&3, &"C")]);
+    /// ```
+    #[inline]
+    pub fn symmetric_difference<'a>(&'a self, other: &'a Self) -> SymmetricDifference<'a, K, V, C> {
+        SymmetricDifference::new(&self.base, &other.base, &self.cmp)
+    }
+
+    /// Returns a [`Cursor`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Bound::Unbounded`] will return a cursor pointing at the first
+    /// element of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let map: VecBTreeMap<_, _> = [(1, "a"), (2, "b"), (3, "c"), (4, "d")].into_iter().collect();
+    ///
+    /// let mut cursor = map.lower_bound(Bound::Included(&2));
+    /// assert_eq!(cursor.key(), Some(&2));
+    /// assert_eq!(cursor.peek_prev(), Some((&1, &"a")));
+    ///
+    /// cursor = map.lower_bound(Bound::Excluded(&2));
+    /// assert_eq!(cursor.key(), Some(&3));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.key(), Some(&4));
+    /// cursor.move_next();
+    /// assert_eq!(cursor.key(), None);
+    /// ```
+    #[inline]
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        Cursor::new(
+            &self.base,
+            lower_bound_index(bound, |k| self.equal_range(k)),
+        )
+    }
+
+    /// Returns a [`CursorMut`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Bound::Unbounded`] will return a cursor pointing at the first
+    /// element of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map: VecBTreeMap<_, _> = [(1, "a"), (3, "c"), (4, "d")].into_iter().collect();
+    ///
+    /// let mut cursor = map.lower_bound_mut(Bound::Included(&2));
+    /// assert_eq!(cursor.key(), Some(&3));
+    /// assert_eq!(cursor.insert_before(2, "b"), None);
+    /// assert_eq!(cursor.insert_before(5, "e"), Some((5, "e")));
+    /// assert_eq!(cursor.remove_current(), Some((3, "c")));
+    /// assert_eq!(cursor.key(), Some(&4));
+    ///
+    /// assert_eq!(map.into_vec(), [(1, "a"), (2, "b"), (4, "d")]);
+    /// ```
+    #[inline]
+    pub fn lower_bound_mut<Q>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, C, A>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let index = lower_bound_index(bound, |k| self.equal_range(k));
+        CursorMut::new(self, index)
+    }
+
+    /// Returns a [`Cursor`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Bound::Unbounded`] will return a cursor pointing at the last
+    /// element of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let map: VecBTreeMap<_, _> = [(1, "a"), (2, "b"), (3, "c"), (4, "d")].into_iter().collect();
+    ///
+    /// let mut cursor = map.upper_bound(Bound::Included(&3));
+    /// assert_eq!(cursor.key(), Some(&3));
+    /// assert_eq!(cursor.peek_next(), Some((&4, &"d")));
+    ///
+    /// cursor = map.upper_bound(Bound::Excluded(&3));
+    /// assert_eq!(cursor.key(), Some(&2));
+    /// cursor.move_prev();
+    /// cursor.move_prev();
+    /// assert_eq!(cursor.key(), None);
+    /// ```
+    #[inline]
+    pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> Cursor<'_, K, V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        Cursor::new(
+            &self.base,
+            upper_bound_index(bound, self.len(), |k| self.equal_range(k)),
+        )
+    }
+
+    /// Returns a [`CursorMut`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// If no such element exists then a cursor pointing at the "ghost"
+    /// non-element is returned.
+    ///
+    /// Passing [`Bound::Unbounded`] will return a cursor pointing at the last
+    /// element of the map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map: VecBTreeMap<_, _> = [(1, "a"), (2, "b"), (4, "d")].into_iter().collect();
+    ///
+    /// let mut cursor = map.upper_bound_mut(Bound::Excluded(&4));
+    /// assert_eq!(cursor.key(), Some(&2));
+    /// assert_eq!(cursor.insert_after(3, "c"), None);
+    /// if let Some((_, v)) = cursor.peek_next() {
+    ///     *v = "C";
+    /// }
+    ///
+    /// assert_eq!(map.into_vec(), [(1, "a"), (2, "b"), (3, "C"), (4, "d")]);
+    /// ```
+    #[inline]
+    pub fn upper_bound_mut<Q>(&mut self, bound: Bound<&Q>) -> CursorMut<'_, K, V, C, A>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let index = upper_bound_index(bound, self.len(), |k| self.equal_range(k));
+        CursorMut::new(self, index)
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    ///
+    /// The position of the key is looked up once, so a read-modify-write
+    /// through the returned [`Entry`] only searches the map a single time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut count = VecBTreeMap::new();
+    ///
+    /// // count the number of occurrences of letters in the vec
+    /// for x in ["a", "b", "a", "c", "a", "b"] {
+    ///     count.entry(x).and_modify(|curr| *curr += 1).or_insert(1);
+    /// }
+    ///
+    /// assert_eq!(count.get("a"), Some(&3));
+    /// assert_eq!(count.get("b"), Some(&2));
+    /// assert_eq!(count.get("c"), Some(&1));
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V, C, A> {
+        match self.binary_search(&key) {
+            Ok(i) => Entry::Occupied(OccupiedEntry::new(self, i)),
+            Err(i) => Entry::Vacant(VacantEntry::new(self, key, i)),
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other
+    /// [`Comparable`] type, but the ordering *must* match the ordering of
+    /// the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert("a", 1);
+    /// assert_eq!(map.remove("a"), Some(1));
+    /// assert_eq!(map.remove("a"), None);
+    /// ```
+    #[inline]
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.binary_search(k).map(|i| self.base.remove(i).1).ok()
+    }
+
+    /// Removes the last key-value pair from the map and returns it, or [`None`] if it
+    /// is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map = VecBTreeMap::new();
+    /// map.insert("a", 1);
+    /// assert_eq!(map.pop(), Some(("a", 1)));
+    /// assert_eq!(map.pop(), None);
+    /// ```
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.base.pop()
+    }
+
+    /// Merges the sorted and deduplicated `other` into the map in `O(n + m)`.
+    /// On equal keys the key of `self` is kept and the value of `other` wins.
+    fn merge<B: Allocator>(&mut self, other: AllocVec<(K, V), B>)
+    where
+        A: Clone,
+    {
+        extend::merge(&mut self.base, other, &self.cmp);
+    }
+
+    /// Moves all key-value pairs from `other` into `self`, leaving `other` empty.
+    ///
+    /// If a key from `other` is already present in `self`, the respective
+    /// value from `self` will be overwritten with the respective value from `other`.
+    ///
+    /// Both maps are merged in `O(n + m)`. If all keys of `other` are greater than
+    /// the keys of `self`, the pairs are simply moved to the back of `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut a: VecBTreeMap<_, _> = [(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+    /// let mut b: VecBTreeMap<_, _> = [(3, "d"), (4, "e"), (5, "f")].into_iter().collect();
+    ///
+    /// a.append(&mut b);
+    ///
+    /// assert_eq!(a.len(), 5);
+    /// assert_eq!(b.len(), 0);
+    ///
+    /// assert_eq!(a.get(&1), Some(&"a"));
+    /// assert_eq!(a.get(&3), Some(&"d")); // Note: "c" has been overwritten.
+    /// assert_eq!(a.get(&5), Some(&"f"));
+    /// ```
+    #[inline]
+    pub fn append(&mut self, other: &mut Self)
+    where
+        A: Clone,
+    {
+        let alloc = other.base.allocator().clone();
+        self.merge(mem::replace(&mut other.base, AllocVec::new_in(alloc)));
+    }
+
+    /// Splits the map into two at the given key. Returns everything after the given key,
+    /// including the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other
+    /// [`Comparable`] type, but the ordering *must* match the ordering of
+    /// the key type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut a: VecBTreeMap<_, _> = [(1, "a"), (2, "b"), (3, "c"), (17, "d"), (41, "e")]
+    ///     .into_iter()
+    ///     .collect();
+    ///
+    /// let b = a.split_off(&3);
+    ///
+    /// assert_eq!(a.into_vec(), [(1, "a"), (2, "b")]);
+    /// assert_eq!(b.into_vec(), [(3, "c"), (17, "d"), (41, "e")]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn split_off<Q>(&mut self, k: &Q) -> Self
+    where
+        Q: Comparable<K, C> + ?Sized,
+        C: Clone,
+        A: Clone,
+    {
+        let at = self.equal_range(k).start;
+        self.split_off_index(at)
+    }
+
+    /// Clears the map, removing all key-value pairs. Keeps the allocated memory
+    /// for reuse.
+    ///
+    /// # Examples
+    ///
+    /// ```
     /// use vec_btree_map::VecBTreeMap;
     ///
     /// let mut a = VecBTreeMap::new();
@@ -389,18 +1672,98 @@ where
     }
 }
 
-impl<K: Clone, V: Clone> Clone for VecBTreeMap<K, V> {
+#[cfg(feature = "alloc")]
+impl<K: Clone, V: Clone, C: Clone, A: Allocator + Clone> Clone for VecBTreeMap<K, V, C, A> {
     #[inline]
     fn clone(&self) -> Self {
         Self {
             base: self.base.clone(),
+            cmp: self.cmp.clone(),
         }
     }
 }
 
-impl<K: Debug, V: Debug> Debug for VecBTreeMap<K, V> {
+#[cfg(feature = "alloc")]
+impl<K: PartialEq, V: PartialEq, C, A: Allocator> PartialEq for VecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.base == other.base
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Eq, V: Eq, C, A: Allocator> Eq for VecBTreeMap<K, V, C, A> {}
+
+#[cfg(feature = "alloc")]
+impl<K: PartialOrd, V: PartialOrd, C, A: Allocator> PartialOrd for VecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (*self.base).partial_cmp(&*other.base)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Ord, V: Ord, C, A: Allocator> Ord for VecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (*self.base).cmp(&*other.base)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Hash, V: Hash, C, A: Allocator> Hash for VecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.base.hash(state)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K: Debug, V: Debug, C, A: Allocator> Debug for VecBTreeMap<K, V, C, A> {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         f.debug_map().entries(self.iter()).finish()
     }
 }
+
+#[cfg(feature = "alloc")]
+impl<K, V, C> From<VecBTreeMap<K, V, C>> for Vec<(K, V)> {
+    /// Takes over the map's vec without copying.
+    #[inline]
+    fn from(map: VecBTreeMap<K, V, C>) -> Self {
+        vec::into_std(map.base)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<K, V, C, A: Allocator> IntoIterator for VecBTreeMap<K, V, C, A> {
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, A>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter::new(self.base.into_iter())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, C, A: Allocator> IntoIterator for &'a VecBTreeMap<K, V, C, A> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, K, V, C, A: Allocator> IntoIterator for &'a mut VecBTreeMap<K, V, C, A> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
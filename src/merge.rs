@@ -0,0 +1,333 @@
+use crate::{Comparator, Natural};
+use core::cmp::Ordering;
+use core::fmt::{Debug, Formatter, Result};
+use core::iter::{FusedIterator, Iterator};
+use core::marker::PhantomData;
+
+mod sealed {
+    /// A sorted run of key-value pairs that the set operations walk from the front.
+    pub trait Pairs<'a, K, V>: Copy {
+        /// Returns the number of pairs left in the run.
+        fn len(&self) -> usize;
+
+        /// Returns the first pair of the run.
+        fn first(&self) -> Option<(&'a K, &'a V)>;
+
+        /// Removes and returns the first pair of the run.
+        fn take_first(&mut self) -> Option<(&'a K, &'a V)>;
+    }
+}
+
+use sealed::Pairs;
+
+impl<'a, K, V> Pairs<'a, K, V> for &'a [(K, V)] {
+    #[inline]
+    fn len(&self) -> usize {
+        <[_]>::len(self)
+    }
+
+    #[inline]
+    fn first(&self) -> Option<(&'a K, &'a V)> {
+        <[_]>::first(self).map(|e| (&e.0, &e.1))
+    }
+
+    #[inline]
+    fn take_first(&mut self) -> Option<(&'a K, &'a V)> {
+        let (first, rest) = self.split_first()?;
+        *self = rest;
+        Some((&first.0, &first.1))
+    }
+}
+
+/// The keys and values of a [`SoaVecBTreeMap`](crate::SoaVecBTreeMap), which always have
+/// the same length.
+impl<'a, K, V> Pairs<'a, K, V> for (&'a [K], &'a [V]) {
+    #[inline]
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    fn first(&self) -> Option<(&'a K, &'a V)> {
+        Some((self.0.first()?, self.1.first()?))
+    }
+
+    #[inline]
+    fn take_first(&mut self) -> Option<(&'a K, &'a V)> {
+        let (k, keys) = self.0.split_first()?;
+        let (v, values) = self.1.split_first()?;
+        *self = (keys, values);
+        Some((k, v))
+    }
+}
+
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Union<'a, K, V, C = Natural, P = &'a [(K, V)]> {
+    a: P,
+    b: P,
+    cmp: &'a C,
+    marker: PhantomData<&'a (K, V)>,
+}
+
+impl<'a, K, V, C, P> Union<'a, K, V, C, P> {
+    #[inline]
+    pub(super) const fn new(a: P, b: P, cmp: &'a C) -> Self {
+        Self {
+            a,
+            b,
+            cmp,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V, C, P: Copy> Clone for Union<'_, K, V, C, P> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.a, self.b, self.cmp)
+    }
+}
+
+impl<'a, K: Debug, V: Debug, C: Comparator<K>, P: Pairs<'a, K, V>> Debug for Union<'a, K, V, C, P> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V, C: Comparator<K>, P: Pairs<'a, K, V>> Iterator for Union<'a, K, V, C, P> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match (self.a.first(), self.b.first()) {
+            (Some(x), Some(y)) => match self.cmp.compare(x.0, y.0) {
+                Ordering::Less => self.a.take_first(),
+                Ordering::Greater => self.b.take_first(),
+                Ordering::Equal => {
+                    self.b.take_first();
+                    self.a.take_first()
+                }
+            },
+            (Some(_), None) => self.a.take_first(),
+            (None, _) => self.b.take_first(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a, b) = (self.a.len(), self.b.len());
+        (a.max(b), a.checked_add(b))
+    }
+}
+
+impl<'a, K, V, C: Comparator<K>, P: Pairs<'a, K, V>> FusedIterator for Union<'a, K, V, C, P> {}
+
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Intersection<'a, K, V, C = Natural, P = &'a [(K, V)]> {
+    a: P,
+    b: P,
+    cmp: &'a C,
+    marker: PhantomData<&'a (K, V)>,
+}
+
+impl<'a, K, V, C, P> Intersection<'a, K, V, C, P> {
+    #[inline]
+    pub(super) const fn new(a: P, b: P, cmp: &'a C) -> Self {
+        Self {
+            a,
+            b,
+            cmp,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V, C, P: Copy> Clone for Intersection<'_, K, V, C, P> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.a, self.b, self.cmp)
+    }
+}
+
+impl<'a, K: Debug, V: Debug, C: Comparator<K>, P: Pairs<'a, K, V>> Debug
+    for Intersection<'a, K, V, C, P>
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V, C: Comparator<K>, P: Pairs<'a, K, V>> Iterator for Intersection<'a, K, V, C, P> {
+    type Item = (&'a K, &'a V, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while let (Some(x), Some(y)) = (self.a.first(), self.b.first()) {
+            match self.cmp.compare(x.0, y.0) {
+                Ordering::Less => {
+                    self.a.take_first();
+                }
+                Ordering::Greater => {
+                    self.b.take_first();
+                }
+                Ordering::Equal => {
+                    self.a.take_first();
+                    self.b.take_first();
+                    return Some((x.0, x.1, y.1));
+                }
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.a.len().min(self.b.len())))
+    }
+}
+
+impl<'a, K, V, C: Comparator<K>, P: Pairs<'a, K, V>> FusedIterator
+    for Intersection<'a, K, V, C, P>
+{
+}
+
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Difference<'a, K, V, C = Natural, P = &'a [(K, V)]> {
+    a: P,
+    b: P,
+    cmp: &'a C,
+    marker: PhantomData<&'a (K, V)>,
+}
+
+impl<'a, K, V, C, P> Difference<'a, K, V, C, P> {
+    #[inline]
+    pub(super) const fn new(a: P, b: P, cmp: &'a C) -> Self {
+        Self {
+            a,
+            b,
+            cmp,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V, C, P: Copy> Clone for Difference<'_, K, V, C, P> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.a, self.b, self.cmp)
+    }
+}
+
+impl<'a, K: Debug, V: Debug, C: Comparator<K>, P: Pairs<'a, K, V>> Debug
+    for Difference<'a, K, V, C, P>
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V, C: Comparator<K>, P: Pairs<'a, K, V>> Iterator for Difference<'a, K, V, C, P> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(x) = self.a.first() {
+            match self.b.first().map(|y| self.cmp.compare(x.0, y.0)) {
+                Some(Ordering::Greater) => {
+                    self.b.take_first();
+                }
+                Some(Ordering::Equal) => {
+                    self.a.take_first();
+                    self.b.take_first();
+                }
+                Some(Ordering::Less) | None => {
+                    self.a.take_first();
+                    return Some(x);
+                }
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a, b) = (self.a.len(), self.b.len());
+        (a.saturating_sub(b), Some(a))
+    }
+}
+
+impl<'a, K, V, C: Comparator<K>, P: Pairs<'a, K, V>> FusedIterator for Difference<'a, K, V, C, P> {}
+
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SymmetricDifference<'a, K, V, C = Natural, P = &'a [(K, V)]> {
+    a: P,
+    b: P,
+    cmp: &'a C,
+    marker: PhantomData<&'a (K, V)>,
+}
+
+impl<'a, K, V, C, P> SymmetricDifference<'a, K, V, C, P> {
+    #[inline]
+    pub(super) const fn new(a: P, b: P, cmp: &'a C) -> Self {
+        Self {
+            a,
+            b,
+            cmp,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V, C, P: Copy> Clone for SymmetricDifference<'_, K, V, C, P> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.a, self.b, self.cmp)
+    }
+}
+
+impl<'a, K: Debug, V: Debug, C: Comparator<K>, P: Pairs<'a, K, V>> Debug
+    for SymmetricDifference<'a, K, V, C, P>
+{
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_list().entries(self.clone()).finish()
+    }
+}
+
+impl<'a, K, V, C: Comparator<K>, P: Pairs<'a, K, V>> Iterator
+    for SymmetricDifference<'a, K, V, C, P>
+{
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            return match (self.a.first(), self.b.first()) {
+                (Some(x), Some(y)) => match self.cmp.compare(x.0, y.0) {
+                    Ordering::Less => self.a.take_first(),
+                    Ordering::Greater => self.b.take_first(),
+                    Ordering::Equal => {
+                        self.a.take_first();
+                        self.b.take_first();
+                        continue;
+                    }
+                },
+                (Some(_), None) => self.a.take_first(),
+                (None, _) => self.b.take_first(),
+            };
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.a.len().checked_add(self.b.len()))
+    }
+}
+
+impl<'a, K, V, C: Comparator<K>, P: Pairs<'a, K, V>> FusedIterator
+    for SymmetricDifference<'a, K, V, C, P>
+{
+}
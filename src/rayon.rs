@@ -0,0 +1,333 @@
+use crate::extend::{dedup, merge};
+use crate::{Comparator, VecBTreeMap, vec};
+use alloc::vec::Vec;
+use allocator_api2::alloc::Allocator;
+use allocator_api2::vec::Vec as AllocVec;
+use rayon::iter::plumbing::{Consumer, ProducerCallback, UnindexedConsumer};
+use rayon::iter::{
+    FromParallelIterator, IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator,
+    IntoParallelRefMutIterator, ParallelExtend, ParallelIterator,
+};
+use rayon::slice::ParallelSliceMut;
+use rayon::{iter, slice};
+
+/// Implements [`ParallelIterator`] and [`IndexedParallelIterator`] for a wrapper
+/// around another indexed parallel iterator.
+macro_rules! delegate_indexed {
+    ($name:ident<$($lt:lifetime,)? $($ty:ident),*>, $item:ty, where $($bounds:tt)*) => {
+        impl<$($lt,)? $($ty),*> ParallelIterator for $name<$($lt,)? $($ty),*>
+        where
+            $($bounds)*
+        {
+            type Item = $item;
+
+            #[inline]
+            fn drive_unindexed<Co>(self, consumer: Co) -> Co::Result
+            where
+                Co: UnindexedConsumer<Self::Item>,
+            {
+                self.inner.drive_unindexed(consumer)
+            }
+
+            #[inline]
+            fn opt_len(&self) -> Option<usize> {
+                self.inner.opt_len()
+            }
+        }
+
+        impl<$($lt,)? $($ty),*> IndexedParallelIterator for $name<$($lt,)? $($ty),*>
+        where
+            $($bounds)*
+        {
+            #[inline]
+            fn drive<Co>(self, consumer: Co) -> Co::Result
+            where
+                Co: Consumer<Self::Item>,
+            {
+                self.inner.drive(consumer)
+            }
+
+            #[inline]
+            fn len(&self) -> usize {
+                self.inner.len()
+            }
+
+            #[inline]
+            fn with_producer<CB>(self, callback: CB) -> CB::Output
+            where
+                CB: ProducerCallback<Self::Item>,
+            {
+                self.inner.with_producer(callback)
+            }
+        }
+    };
+}
+
+/// Maps every pair of a slice in parallel.
+type MapPairs<'a, K, V, T> = iter::Map<slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> T>;
+
+/// Maps every pair of a mutable slice in parallel.
+type MapPairsMut<'a, K, V, T> = iter::Map<slice::IterMut<'a, (K, V)>, fn(&'a mut (K, V)) -> T>;
+
+/// A parallel iterator over the entries of a [`VecBTreeMap`].
+///
+/// This `struct` is created by [`VecBTreeMap::par_iter`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone)]
+pub struct ParIter<'a, K, V> {
+    inner: MapPairs<'a, K, V, (&'a K, &'a V)>,
+}
+
+delegate_indexed!(ParIter<'a, K, V>, (&'a K, &'a V), where K: Sync, V: Sync);
+
+/// A mutable parallel iterator over the entries of a [`VecBTreeMap`].
+///
+/// This `struct` is created by [`VecBTreeMap::par_iter_mut`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Debug)]
+pub struct ParIterMut<'a, K, V> {
+    inner: MapPairsMut<'a, K, V, (&'a K, &'a mut V)>,
+}
+
+delegate_indexed!(ParIterMut<'a, K, V>, (&'a K, &'a mut V), where K: Send + Sync, V: Send);
+
+/// An owning parallel iterator over the entries of a [`VecBTreeMap`].
+///
+/// This `struct` is created by the [`into_par_iter`] method on [`VecBTreeMap`]
+/// (provided by the [`IntoParallelIterator`] trait).
+///
+/// [`into_par_iter`]: IntoParallelIterator::into_par_iter
+#[derive(Debug, Clone)]
+pub struct IntoParIter<K, V> {
+    inner: rayon::vec::IntoIter<(K, V)>,
+}
+
+delegate_indexed!(IntoParIter<K, V>, (K, V), where K: Send, V: Send);
+
+/// A parallel iterator over the keys of a [`VecBTreeMap`].
+///
+/// This `struct` is created by [`VecBTreeMap::par_keys`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone)]
+pub struct ParKeys<'a, K, V> {
+    inner: MapPairs<'a, K, V, &'a K>,
+}
+
+delegate_indexed!(ParKeys<'a, K, V>, &'a K, where K: Sync, V: Sync);
+
+/// A parallel iterator over the values of a [`VecBTreeMap`].
+///
+/// This `struct` is created by [`VecBTreeMap::par_values`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Debug, Clone)]
+pub struct ParValues<'a, K, V> {
+    inner: MapPairs<'a, K, V, &'a V>,
+}
+
+delegate_indexed!(ParValues<'a, K, V>, &'a V, where K: Sync, V: Sync);
+
+/// A mutable parallel iterator over the values of a [`VecBTreeMap`].
+///
+/// This `struct` is created by [`VecBTreeMap::par_values_mut`].
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+#[derive(Debug)]
+pub struct ParValuesMut<'a, K, V> {
+    inner: MapPairsMut<'a, K, V, &'a mut V>,
+}
+
+delegate_indexed!(ParValuesMut<'a, K, V>, &'a mut V, where K: Send, V: Send);
+
+impl<K, V, C, A: Allocator> VecBTreeMap<K, V, C, A> {
+    /// Gets a parallel iterator over the entries of the map.
+    ///
+    /// The iterator is indexed, so it can be zipped, split and collected in order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let map: VecBTreeMap<_, _> = (0..1000).map(|i| (i, i * 2)).collect();
+    /// let sum: i32 = map.par_iter().map(|(_, v)| *v).sum();
+    /// assert_eq!(sum, 999_000);
+    /// ```
+    #[inline]
+    pub fn par_iter(&self) -> ParIter<'_, K, V>
+    where
+        K: Sync,
+        V: Sync,
+    {
+        ParIter {
+            inner: self.base.par_iter().map(|e| (&e.0, &e.1)),
+        }
+    }
+
+    /// Gets a mutable parallel iterator over the entries of the map.
+    #[inline]
+    pub fn par_iter_mut(&mut self) -> ParIterMut<'_, K, V>
+    where
+        K: Send + Sync,
+        V: Send,
+    {
+        ParIterMut {
+            inner: self.base.par_iter_mut().map(|e| (&e.0, &mut e.1)),
+        }
+    }
+
+    /// Gets a parallel iterator over the keys of the map, in sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let map: VecBTreeMap<_, _> = [(2, "b"), (1, "a")].into_iter().collect();
+    /// let keys: Vec<_> = map.par_keys().collect();
+    /// assert_eq!(keys, [&1, &2]);
+    /// ```
+    #[inline]
+    pub fn par_keys(&self) -> ParKeys<'_, K, V>
+    where
+        K: Sync,
+        V: Sync,
+    {
+        ParKeys {
+            inner: self.base.par_iter().map(|e| &e.0),
+        }
+    }
+
+    /// Gets a parallel iterator over the values of the map, in order by key.
+    #[inline]
+    pub fn par_values(&self) -> ParValues<'_, K, V>
+    where
+        K: Sync,
+        V: Sync,
+    {
+        ParValues {
+            inner: self.base.par_iter().map(|e| &e.1),
+        }
+    }
+
+    /// Gets a mutable parallel iterator over the values of the map, in order by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rayon::prelude::*;
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut map: VecBTreeMap<_, _> = (0..100).map(|i| (i, i)).collect();
+    /// map.par_values_mut().for_each(|v| *v *= 10);
+    /// assert_eq!(map.get(&7), Some(&70));
+    /// ```
+    #[inline]
+    pub fn par_values_mut(&mut self) -> ParValuesMut<'_, K, V>
+    where
+        K: Send,
+        V: Send,
+    {
+        ParValuesMut {
+            inner: self.base.par_iter_mut().map(|e| &mut e.1),
+        }
+    }
+}
+
+impl<'a, K: Sync, V: Sync, C, A: Allocator> IntoParallelIterator for &'a VecBTreeMap<K, V, C, A> {
+    type Item = (&'a K, &'a V);
+    type Iter = ParIter<'a, K, V>;
+
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter()
+    }
+}
+
+impl<'a, K: Send + Sync, V: Send, C, A: Allocator> IntoParallelIterator
+    for &'a mut VecBTreeMap<K, V, C, A>
+{
+    type Item = (&'a K, &'a mut V);
+    type Iter = ParIterMut<'a, K, V>;
+
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        self.par_iter_mut()
+    }
+}
+
+impl<K: Send, V: Send, C> IntoParallelIterator for VecBTreeMap<K, V, C> {
+    type Item = (K, V);
+    type Iter = IntoParIter<K, V>;
+
+    /// Consumes the map in parallel without copying its pairs.
+    ///
+    /// This is only implemented for maps in the global allocator, because rayon's owning
+    /// iterator takes over a [`Vec`], which cannot hold a buffer from another allocator.
+    /// Maps in other allocators can still be iterated in parallel by reference.
+    #[inline]
+    fn into_par_iter(self) -> Self::Iter {
+        IntoParIter {
+            inner: vec::into_std(self.base).into_par_iter(),
+        }
+    }
+}
+
+/// Collects `iter` in parallel, then sorts it in parallel and removes duplicate keys,
+/// keeping the value that came last.
+fn par_collect_sorted<K, V, C, I>(iter: I, cmp: &C) -> AllocVec<(K, V)>
+where
+    K: Send,
+    V: Send,
+    C: Comparator<K> + Sync,
+    I: IntoParallelIterator<Item = (K, V)>,
+{
+    let mut base = vec::from_std(iter.into_par_iter().collect::<Vec<_>>());
+    if !base.is_sorted_by(|a, b| cmp.compare(&a.0, &b.0).is_lt()) {
+        base.par_sort_by(|a, b| cmp.compare(&a.0, &b.0));
+        dedup(&mut base, cmp);
+    }
+    base
+}
+
+impl<K, V, C> FromParallelIterator<(K, V)> for VecBTreeMap<K, V, C>
+where
+    K: Send,
+    V: Send,
+    C: Comparator<K> + Default + Sync,
+{
+    /// Collects the pairs and sorts them in parallel.
+    ///
+    /// If a key occurs more than once, the value that came last in the iterator's
+    /// order is kept, just like with [`FromIterator`].
+    #[inline]
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        let cmp = C::default();
+        Self {
+            base: par_collect_sorted(par_iter, &cmp),
+            cmp,
+        }
+    }
+}
+
+impl<K, V, C, A> ParallelExtend<(K, V)> for VecBTreeMap<K, V, C, A>
+where
+    K: Send,
+    V: Send,
+    C: Comparator<K> + Sync,
+    A: Allocator + Clone,
+{
+    /// Collects the pairs and sorts them in parallel, then merges them into the map
+    /// in `O(n + m)`.
+    #[inline]
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = (K, V)>,
+    {
+        let other = par_collect_sorted(par_iter, &self.cmp);
+        merge(&mut self.base, other, &self.cmp);
+    }
+}
@@ -1,77 +1,289 @@
-use crate::VecBTreeMap;
-use core::fmt;
+//! Serialization and deserialization of [`VecBTreeMap`] and [`VecBTreeSet`].
+//!
+//! Maps are serialized as maps, in order by key. By default, deserialization accepts
+//! keys in any order and lets the last value of a repeated key win, like repeated
+//! calls to [`VecBTreeMap::insert`]. Sorted input is deserialized in `O(n)`, and any
+//! other input is collected first and then sorted once, in `O(n log n)`. The [`strict`], [`last_wins`] and [`first_wins`]
+//! modules choose a policy explicitly, and can be used with `#[serde(with = "...")]`.
+//!
+//! Formats like JSON only support string map keys. The [`as_seq`] module and the
+//! [`SeqVecBTreeMap`] wrapper serialize a map as a sequence of key-value pairs instead.
+//! Human-readable formats like JSON are asked for whatever form the input holds, so
+//! every way of deserializing a map accepts both forms there. Other formats are often
+//! not self-describing and are asked for the form that the same module serializes.
+
+use crate::extend::dedup;
+use crate::{Comparator, Natural, VecBTreeMap, VecBTreeSet};
+use alloc::vec::Vec;
+use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::vec::Vec as AllocVec;
+use core::fmt::{self, Debug};
 use core::marker::PhantomData;
-use serde::de::{MapAccess, SeqAccess, Visitor};
-use serde::ser::SerializeMap;
+use core::ops::{Deref, DerefMut};
+use serde::de::{Error, MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-struct VecBTreeMapVisitor<K, V> {
-    marker: PhantomData<fn() -> VecBTreeMap<K, V>>,
+/// The form a map is serialized in.
+#[derive(Clone, Copy)]
+enum Form {
+    Map,
+    Seq,
 }
 
-impl<K, V> VecBTreeMapVisitor<K, V> {
-    const fn new() -> Self {
+impl Form {
+    /// Asks `deserializer` for a map in this form, or for any form if the format is
+    /// human-readable.
+    fn deserialize<'de, D, T>(self, deserializer: D, visitor: T) -> Result<T::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: Visitor<'de>,
+    {
+        if deserializer.is_human_readable() {
+            return deserializer.deserialize_any(visitor);
+        }
+        match self {
+            Self::Map => deserializer.deserialize_map(visitor),
+            Self::Seq => deserializer.deserialize_seq(visitor),
+        }
+    }
+}
+
+/// What to do with a key that is not greater than the previously deserialized key.
+#[derive(Clone, Copy)]
+enum Policy {
+    Strict,
+    LastWins,
+    FirstWins,
+}
+
+impl Policy {
+    /// Appends the pair at `position` of the input to `base`, and clears `sorted`
+    /// once a key is not greater than its predecessor.
+    fn push<K, V, C, A, E>(
+        self,
+        base: &mut AllocVec<(K, V), A>,
+        cmp: &C,
+        (k, v): (K, V),
+        position: usize,
+        sorted: &mut bool,
+    ) -> Result<(), E>
+    where
+        C: Comparator<K>,
+        A: Allocator,
+        E: Error,
+    {
+        if *sorted
+            && let Some(last) = base.last()
+            && cmp.compare(&last.0, &k).is_ge()
+        {
+            if let Self::Strict = self {
+                return Err(match base.binary_search_by(|e| cmp.compare(&e.0, &k)) {
+                    Ok(_) => E::custom(format_args!("duplicate key at position {position}")),
+                    Err(_) => E::custom(format_args!(
+                        "key at position {position} is not greater than the previous key"
+                    )),
+                });
+            }
+            *sorted = false;
+        }
+        base.push((k, v));
+        Ok(())
+    }
+
+    /// Appends the pairs returned by `next` to the empty `base`, then sorts them by key.
+    fn fill<K, V, C, A, E, F>(
+        self,
+        base: &mut AllocVec<(K, V), A>,
+        cmp: &C,
+        mut next: F,
+    ) -> Result<(), E>
+    where
+        C: Comparator<K>,
+        A: Allocator,
+        E: Error,
+        F: FnMut() -> Result<Option<(K, V)>, E>,
+    {
+        let mut sorted = true;
+        let mut position = 0;
+        while let Some(pair) = next()? {
+            self.push(base, cmp, pair, position, &mut sorted)?;
+            position += 1;
+        }
+        self.finish(base, cmp, sorted);
+        Ok(())
+    }
+
+    /// Sorts the collected pairs by key, unless they already are, and removes
+    /// duplicate keys in `O(n log n)`.
+    fn finish<K, V, C, A>(self, base: &mut AllocVec<(K, V), A>, cmp: &C, sorted: bool)
+    where
+        C: Comparator<K>,
+        A: Allocator,
+    {
+        if sorted {
+            return;
+        }
+        base.sort_by(|a, b| cmp.compare(&a.0, &b.0));
+        match self {
+            Self::Strict | Self::LastWins => dedup(base, cmp),
+            Self::FirstWins => base.dedup_by(|next, kept| cmp.compare(&next.0, &kept.0).is_eq()),
+        }
+    }
+}
+
+struct VecBTreeMapVisitor<K, V, C, A> {
+    cmp: C,
+    alloc: A,
+    policy: Policy,
+    marker: PhantomData<fn() -> (K, V)>,
+}
+
+impl<K, V, C, A> VecBTreeMapVisitor<K, V, C, A> {
+    const fn new(cmp: C, alloc: A, policy: Policy) -> Self {
         Self {
+            cmp,
+            alloc,
+            policy,
             marker: PhantomData,
         }
     }
 }
 
-impl<'de, K, V> Visitor<'de> for VecBTreeMapVisitor<K, V>
+impl<'de, K, V, C, A> Visitor<'de> for VecBTreeMapVisitor<K, V, C, A>
 where
-    K: Deserialize<'de> + Ord,
+    K: Deserialize<'de>,
     V: Deserialize<'de>,
+    C: Comparator<K>,
+    A: Allocator,
 {
-    type Value = VecBTreeMap<K, V>;
+    type Value = VecBTreeMap<K, V, C, A>;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("a map created by VecBTreeMap")
+        formatter.write_str("a map or a sequence of key-value pairs")
     }
 
     fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
     where
         S: SeqAccess<'de>,
     {
-        let mut m = VecBTreeMap::with_capacity(seq.size_hint().unwrap_or(0));
-        while let Some((k, v)) = seq.next_element()? {
-            if let Some((k, v)) = m.push(k, v) {
-                m.insert(k, v);
-            }
-        }
-        Ok(m)
+        let mut base = AllocVec::with_capacity_in(seq.size_hint().unwrap_or(0), self.alloc);
+        self.policy
+            .fill(&mut base, &self.cmp, || seq.next_element())?;
+        Ok(VecBTreeMap {
+            base,
+            cmp: self.cmp,
+        })
     }
 
     fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
     where
         M: MapAccess<'de>,
     {
-        let mut m = VecBTreeMap::with_capacity(map.size_hint().unwrap_or(0));
-        while let Some((k, v)) = map.next_entry()? {
-            if let Some((k, v)) = m.push(k, v) {
-                m.insert(k, v);
-            }
-        }
-        Ok(m)
+        let mut base = AllocVec::with_capacity_in(map.size_hint().unwrap_or(0), self.alloc);
+        self.policy
+            .fill(&mut base, &self.cmp, || map.next_entry())?;
+        Ok(VecBTreeMap {
+            base,
+            cmp: self.cmp,
+        })
+    }
+}
+
+struct VecBTreeMapInPlaceVisitor<'a, K, V, C, A: Allocator> {
+    place: &'a mut VecBTreeMap<K, V, C, A>,
+    policy: Policy,
+}
+
+impl<'a, K, V, C, A: Allocator> VecBTreeMapInPlaceVisitor<'a, K, V, C, A> {
+    const fn new(place: &'a mut VecBTreeMap<K, V, C, A>, policy: Policy) -> Self {
+        Self { place, policy }
+    }
+}
+
+impl<K, V, C, A> VecBTreeMapInPlaceVisitor<'_, K, V, C, A>
+where
+    C: Comparator<K>,
+    A: Allocator,
+{
+    /// Replaces the pairs of the map with the pairs returned by `next`, keeping its
+    /// allocation. The map is left empty on error.
+    fn refill<E, F>(self, size_hint: Option<usize>, next: F) -> Result<(), E>
+    where
+        E: Error,
+        F: FnMut() -> Result<Option<(K, V)>, E>,
+    {
+        let VecBTreeMap { base, cmp } = self.place;
+        base.clear();
+        base.reserve(size_hint.unwrap_or(0));
+        self.policy
+            .fill(base, cmp, next)
+            .inspect_err(|_| base.clear())
+    }
+}
+
+impl<'de, K, V, C, A> Visitor<'de> for VecBTreeMapInPlaceVisitor<'_, K, V, C, A>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    C: Comparator<K>,
+    A: Allocator,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map or a sequence of key-value pairs")
+    }
+
+    fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        self.refill(seq.size_hint(), || seq.next_element())
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        self.refill(map.size_hint(), || map.next_entry())
     }
 }
 
-impl<'de, K, V> Deserialize<'de> for VecBTreeMap<K, V>
+impl<'de, K, V, C, A> Deserialize<'de> for VecBTreeMap<K, V, C, A>
 where
-    K: Deserialize<'de> + Ord,
+    K: Deserialize<'de>,
     V: Deserialize<'de>,
+    C: Comparator<K> + Default,
+    A: Allocator + Default,
 {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_map(VecBTreeMapVisitor::new())
+        last_wins::deserialize(deserializer)
+    }
+
+    /// Deserializes into an existing map, reusing its allocation.
+    ///
+    /// The previous pairs are dropped, and the new ones are handled just like in
+    /// [`deserialize`](Self::deserialize). If deserialization fails, the map is left empty.
+    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Form::Map.deserialize(
+            deserializer,
+            VecBTreeMapInPlaceVisitor::new(place, Policy::LastWins),
+        )
     }
 }
 
-impl<K, V> Serialize for VecBTreeMap<K, V>
+impl<K, V, C, A> Serialize for VecBTreeMap<K, V, C, A>
 where
     K: Serialize,
     V: Serialize,
+    A: Allocator,
 {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -84,3 +296,396 @@ where
         m.end()
     }
 }
+
+/// Deserializes a map with the given policy.
+fn deserialize_with<'de, D, K, V, C, A>(
+    deserializer: D,
+    policy: Policy,
+) -> Result<VecBTreeMap<K, V, C, A>, D::Error>
+where
+    D: Deserializer<'de>,
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    C: Comparator<K> + Default,
+    A: Allocator + Default,
+{
+    let visitor = VecBTreeMapVisitor::new(C::default(), A::default(), policy);
+    Form::Map.deserialize(deserializer, visitor)
+}
+
+macro_rules! policy_module {
+    ($(#[$attr:meta])* $name:ident, $policy:ident) => {
+        $(#[$attr])*
+        pub mod $name {
+            use super::{Policy, deserialize_with};
+            use crate::{Comparator, VecBTreeMap};
+            use allocator_api2::alloc::Allocator;
+            use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+            /// Deserializes a [`VecBTreeMap`] with this module's policy.
+            ///
+            /// # Errors
+            ///
+            /// Returns the deserializer's error if the input is not a valid map.
+            #[inline]
+            pub fn deserialize<'de, D, K, V, C, A>(
+                deserializer: D,
+            ) -> Result<VecBTreeMap<K, V, C, A>, D::Error>
+            where
+                D: Deserializer<'de>,
+                K: Deserialize<'de>,
+                V: Deserialize<'de>,
+                C: Comparator<K> + Default,
+                A: Allocator + Default,
+            {
+                deserialize_with(deserializer, Policy::$policy)
+            }
+
+            /// Serializes a [`VecBTreeMap`] as a map, in order by key.
+            ///
+            /// # Errors
+            ///
+            /// Returns the serializer's error.
+            #[inline]
+            pub fn serialize<S, K, V, C, A>(
+                map: &VecBTreeMap<K, V, C, A>,
+                serializer: S,
+            ) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+                K: Serialize,
+                V: Serialize,
+                A: Allocator,
+            {
+                map.serialize(serializer)
+            }
+        }
+    };
+}
+
+policy_module!(
+    /// Rejects input whose keys are not strictly increasing.
+    ///
+    /// The error names the position of the first key that is either a duplicate or
+    /// out of order. Accepted input is deserialized in `O(n)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// #[derive(Debug, Deserialize)]
+    /// struct Config {
+    ///     #[serde(with = "vec_btree_map::serde::strict")]
+    ///     limits: VecBTreeMap<String, u32>,
+    /// }
+    ///
+    /// let ok = r#"{"limits": {"a": 1, "b": 2}}"#;
+    /// assert_eq!(serde_json::from_str::<Config>(ok).unwrap().limits.len(), 2);
+    ///
+    /// let err = serde_json::from_str::<Config>(r#"{"limits": {"a": 1, "a": 2}}"#);
+    /// assert!(err.unwrap_err().to_string().contains("duplicate key at position 1"));
+    ///
+    /// let err = serde_json::from_str::<Config>(r#"{"limits": {"b": 1, "a": 2}}"#);
+    /// assert!(err.unwrap_err().to_string().contains("key at position 1 is not greater"));
+    /// ```
+    strict,
+    Strict
+);
+
+policy_module!(
+    /// Accepts keys in any order, and keeps the last value of a repeated key.
+    ///
+    /// This is what [`VecBTreeMap`]'s [`Deserialize`] implementation does.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let map: VecBTreeMap<String, u32> = serde_json::from_str(r#"{"b": 1, "a": 2, "b": 3}"#).unwrap();
+    /// assert_eq!(map.get("b"), Some(&3));
+    /// assert_eq!(serde_json::to_string(&map).unwrap(), r#"{"a":2,"b":3}"#);
+    /// ```
+    last_wins,
+    LastWins
+);
+
+policy_module!(
+    /// Accepts keys in any order, and keeps the first value of a repeated key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeMap;
+    ///
+    /// let mut de = serde_json::Deserializer::from_str(r#"{"b": 1, "a": 2, "b": 3}"#);
+    /// let map: VecBTreeMap<String, u32> = vec_btree_map::serde::first_wins::deserialize(&mut de).unwrap();
+    /// assert_eq!(map.get("b"), Some(&1));
+    /// ```
+    first_wins,
+    FirstWins
+);
+
+/// Serializes a [`VecBTreeMap`] as a sequence of key-value pairs, e.g. `[[k, v], ...]`
+/// in JSON.
+///
+/// Unlike a map, this works for keys that the format cannot use as map keys, such as
+/// tuples or structs in JSON. Deserialization keeps the last value of a repeated key.
+///
+/// # Examples
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use vec_btree_map::VecBTreeMap;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Grid {
+///     #[serde(with = "vec_btree_map::serde::as_seq")]
+///     cells: VecBTreeMap<(u8, u8), char>,
+/// }
+///
+/// let grid = Grid { cells: [((0, 1), 'b'), ((0, 0), 'a')].into_iter().collect() };
+/// let json = serde_json::to_string(&grid).unwrap();
+/// assert_eq!(json, r#"{"cells":[[[0,0],"a"],[[0,1],"b"]]}"#);
+///
+/// let grid: Grid = serde_json::from_str(&json).unwrap();
+/// assert_eq!(grid.cells.get(&(0, 1)), Some(&'b'));
+/// ```
+pub mod as_seq {
+    use super::{Form, Policy, VecBTreeMapVisitor};
+    use crate::{Comparator, VecBTreeMap};
+    use allocator_api2::alloc::Allocator;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Deserializes a [`VecBTreeMap`] from a sequence of key-value pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns the deserializer's error if the input is not a valid sequence of pairs.
+    #[inline]
+    pub fn deserialize<'de, D, K, V, C, A>(
+        deserializer: D,
+    ) -> Result<VecBTreeMap<K, V, C, A>, D::Error>
+    where
+        D: Deserializer<'de>,
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+        C: Comparator<K> + Default,
+        A: Allocator + Default,
+    {
+        let visitor = VecBTreeMapVisitor::new(C::default(), A::default(), Policy::LastWins);
+        Form::Seq.deserialize(deserializer, visitor)
+    }
+
+    /// Serializes a [`VecBTreeMap`] as a sequence of key-value pairs, in order by key.
+    ///
+    /// # Errors
+    ///
+    /// Returns the serializer's error.
+    #[inline]
+    pub fn serialize<S, K, V, C, A>(
+        map: &VecBTreeMap<K, V, C, A>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        K: Serialize,
+        V: Serialize,
+        A: Allocator,
+    {
+        serializer.collect_seq(map.iter())
+    }
+}
+
+policy_module!(
+    /// Serializes a [`VecBTreeMap`] as a map, which is what its [`Serialize`]
+    /// implementation does.
+    ///
+    /// This is the counterpart of [`as_seq`], for fields of a
+    /// [`SeqVecBTreeMap`] type that should be a map after all.
+    /// Deserialization keeps the last value of a repeated key.
+    as_map,
+    LastWins
+);
+
+/// A [`VecBTreeMap`] that serializes as a sequence of key-value pairs by default.
+///
+/// See [`as_seq`] for the format. It dereferences to the wrapped map.
+///
+/// # Examples
+///
+/// ```
+/// use vec_btree_map::serde::SeqVecBTreeMap;
+///
+/// let mut map: SeqVecBTreeMap<_, _> = SeqVecBTreeMap::default();
+/// map.insert((1, 2), "a");
+/// let json = serde_json::to_string(&map).unwrap();
+/// assert_eq!(json, r#"[[[1,2],"a"]]"#);
+/// assert_eq!(serde_json::from_str::<SeqVecBTreeMap<_, _>>(&json).unwrap(), map);
+/// ```
+#[derive(Clone)]
+pub struct SeqVecBTreeMap<K, V, C = Natural, A: Allocator = Global>(pub VecBTreeMap<K, V, C, A>);
+
+impl<K, V, C, A: Allocator> SeqVecBTreeMap<K, V, C, A> {
+    /// Unwraps the map.
+    #[inline]
+    pub fn into_inner(self) -> VecBTreeMap<K, V, C, A> {
+        self.0
+    }
+}
+
+impl<K, V, C: Default, A: Allocator + Default> Default for SeqVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn default() -> Self {
+        Self(VecBTreeMap::default())
+    }
+}
+
+impl<K: PartialEq, V: PartialEq, C, A: Allocator> PartialEq for SeqVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<K: Eq, V: Eq, C, A: Allocator> Eq for SeqVecBTreeMap<K, V, C, A> {}
+
+impl<K, V, C, A: Allocator> Deref for SeqVecBTreeMap<K, V, C, A> {
+    type Target = VecBTreeMap<K, V, C, A>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<K, V, C, A: Allocator> DerefMut for SeqVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<K: Debug, V: Debug, C, A: Allocator> Debug for SeqVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<K, V, C, A: Allocator> From<VecBTreeMap<K, V, C, A>> for SeqVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn from(map: VecBTreeMap<K, V, C, A>) -> Self {
+        Self(map)
+    }
+}
+
+impl<K, V, C, A: Allocator> From<SeqVecBTreeMap<K, V, C, A>> for VecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn from(map: SeqVecBTreeMap<K, V, C, A>) -> Self {
+        map.0
+    }
+}
+
+impl<'de, K, V, C, A> Deserialize<'de> for SeqVecBTreeMap<K, V, C, A>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>,
+    C: Comparator<K> + Default,
+    A: Allocator + Default,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        as_seq::deserialize(deserializer).map(Self)
+    }
+
+    fn deserialize_in_place<D>(deserializer: D, place: &mut Self) -> Result<(), D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Form::Seq.deserialize(
+            deserializer,
+            VecBTreeMapInPlaceVisitor::new(&mut place.0, Policy::LastWins),
+        )
+    }
+}
+
+impl<K, V, C, A> Serialize for SeqVecBTreeMap<K, V, C, A>
+where
+    K: Serialize,
+    V: Serialize,
+    A: Allocator,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        as_seq::serialize(&self.0, serializer)
+    }
+}
+
+struct VecBTreeSetVisitor<T> {
+    marker: PhantomData<fn() -> VecBTreeSet<T>>,
+}
+
+impl<T> VecBTreeSetVisitor<T> {
+    const fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> Visitor<'de> for VecBTreeSetVisitor<T>
+where
+    T: Deserialize<'de> + Ord,
+{
+    type Value = VecBTreeSet<T>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence created by VecBTreeSet")
+    }
+
+    fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
+    where
+        S: SeqAccess<'de>,
+    {
+        let mut base = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(v) = seq.next_element()? {
+            base.push(v);
+        }
+        Ok(base.into_iter().collect())
+    }
+}
+
+impl<'de, T> Deserialize<'de> for VecBTreeSet<T>
+where
+    T: Deserialize<'de> + Ord,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(VecBTreeSetVisitor::new())
+    }
+}
+
+impl<T> Serialize for VecBTreeSet<T>
+where
+    T: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_seq(Some(self.len()))?;
+        for v in self.iter() {
+            s.serialize_element(v)?;
+        }
+        s.end()
+    }
+}
@@ -0,0 +1,392 @@
+use crate::extend::merge_by;
+use crate::{Comparable, Natural, equal_range, range_indices};
+use allocator_api2::vec::{self, Vec};
+use core::fmt::{self, Debug, Formatter};
+use core::ops::{self, Deref, RangeBounds};
+use core::slice;
+
+/// Basically just a sorted `Vec` that can be used as a set.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VecBTreeSet<T> {
+    base: Vec<T>,
+}
+
+impl<T> Default for VecBTreeSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> VecBTreeSet<T> {
+    /// Constructs a new, empty `VecBTreeSet<T>`.
+    ///
+    /// The set is initially created with a capacity of 0, so it will not allocate until it is first inserted into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_mut)]
+    /// use vec_btree_map::VecBTreeSet;
+    ///
+    /// let mut set: VecBTreeSet<i32> = VecBTreeSet::new();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { base: Vec::new() }
+    }
+
+    /// Constructs a new, empty `VecBTreeSet<T>` with at least the specified capacity.
+    ///
+    /// See [`VecBTreeMap::with_capacity`](crate::VecBTreeMap::with_capacity) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeSet;
+    ///
+    /// let set: VecBTreeSet<i32> = VecBTreeSet::with_capacity(10);
+    /// assert!(set.capacity() >= 10);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            base: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of elements the set can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.base.capacity()
+    }
+
+    /// An iterator yielding all values from start to end.
+    /// The iterator element type is `&T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeSet;
+    ///
+    /// let set: VecBTreeSet<_> = [3, 1, 2].into_iter().collect();
+    /// let mut iter = set.iter();
+    ///
+    /// assert_eq!(iter.next(), Some(&1));
+    /// assert_eq!(iter.next(), Some(&2));
+    /// assert_eq!(iter.next(), Some(&3));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.base.iter()
+    }
+
+    /// Returns a reference to the first, and therefore smallest, value in the set,
+    /// or [`None`] if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeSet;
+    ///
+    /// let mut set = VecBTreeSet::new();
+    /// assert_eq!(set.first(), None);
+    /// set.insert(2);
+    /// set.insert(1);
+    /// assert_eq!(set.first(), Some(&1));
+    /// ```
+    #[inline]
+    pub fn first(&self) -> Option<&T> {
+        self.base.first()
+    }
+
+    /// Returns a reference to the last, and therefore largest, value in the set,
+    /// or [`None`] if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeSet;
+    ///
+    /// let mut set = VecBTreeSet::new();
+    /// assert_eq!(set.last(), None);
+    /// set.insert(1);
+    /// set.insert(2);
+    /// assert_eq!(set.last(), Some(&2));
+    /// ```
+    #[inline]
+    pub fn last(&self) -> Option<&T> {
+        self.base.last()
+    }
+
+    /// Consumes the set and returns the underlying vec of values, sorted in ascending order.
+    ///
+    /// This does not copy or reallocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeSet;
+    ///
+    /// let set: VecBTreeSet<_> = [2, 1].into_iter().collect();
+    /// assert_eq!(set.clone().into_vec(), [1, 2]);
+    /// assert_eq!(Vec::from(set), [1, 2]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn into_vec(self) -> Vec<T> {
+        self.base
+    }
+}
+
+impl<T> VecBTreeSet<T>
+where
+    T: Ord,
+{
+    /// Binary searches this set for a given value.
+    ///
+    /// If the value is found then [`Result::Ok`] is returned, containing its index.
+    /// If the value is not found then [`Result::Err`] is returned, containing
+    /// the index where it could be inserted while maintaining sorted order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeSet;
+    ///
+    /// let set: VecBTreeSet<_> = ["a", "c", "d"].into_iter().collect();
+    ///
+    /// assert_eq!(set.binary_search("a"), Ok(0));
+    /// assert_eq!(set.binary_search("b"), Err(1));
+    /// assert_eq!(set.binary_search("e"), Err(3));
+    /// ```
+    #[inline]
+    pub fn binary_search<Q>(&self, value: &Q) -> Result<usize, usize>
+    where
+        Q: Comparable<T> + ?Sized,
+    {
+        self.base
+            .binary_search_by(|e| value.compare(e, &Natural).reverse())
+    }
+
+    /// Returns the positions of all elements that compare equal to `value`.
+    #[inline]
+    fn equal_range<Q>(&self, value: &Q) -> ops::Range<usize>
+    where
+        Q: Comparable<T> + ?Sized,
+    {
+        equal_range(&self.base, |e| value.compare(e, &Natural).reverse())
+    }
+
+    /// Returns `true` if the set contains the value.
+    ///
+    /// The value may be any borrowed form of the set's value type, or any other
+    /// [`Comparable`] type, but the ordering *must* match the ordering of
+    /// the value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeSet;
+    ///
+    /// let set: VecBTreeSet<_> = [1, 2, 3].into_iter().collect();
+    /// assert_eq!(set.contains(&1), true);
+    /// assert_eq!(set.contains(&4), false);
+    /// ```
+    #[inline]
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        Q: Comparable<T> + ?Sized,
+    {
+        self.binary_search(value).is_ok()
+    }
+
+    /// Adds a value to the set.
+    ///
+    /// Returns whether the value was newly inserted. That is:
+    ///
+    /// - If the set did not previously contain an equal value, `true` is returned.
+    /// - If the set already contained an equal value, `false` is returned, and
+    ///   the entry is not updated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeSet;
+    ///
+    /// let mut set = VecBTreeSet::new();
+    ///
+    /// assert_eq!(set.insert(2), true);
+    /// assert_eq!(set.insert(2), false);
+    /// assert_eq!(set.len(), 1);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, value: T) -> bool {
+        match self.binary_search(&value) {
+            Ok(_) => false,
+            Err(i) => {
+                self.base.insert(i, value);
+                true
+            }
+        }
+    }
+
+    /// If the set contains an element equal to the value, removes it from the
+    /// set and returns `true`.
+    ///
+    /// The value may be any borrowed form of the set's value type, or any other
+    /// [`Comparable`] type, but the ordering *must* match the ordering of
+    /// the value type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeSet;
+    ///
+    /// let mut set = VecBTreeSet::new();
+    ///
+    /// set.insert(2);
+    /// assert_eq!(set.remove(&2), true);
+    /// assert_eq!(set.remove(&2), false);
+    /// ```
+    #[inline]
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        Q: Comparable<T> + ?Sized,
+    {
+        self.binary_search(value)
+            .map(|i| self.base.remove(i))
+            .is_ok()
+    }
+
+    /// Removes the last value from the set and returns it, or [`None`] if it
+    /// is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        self.base.pop()
+    }
+
+    /// Clears the set, removing all values. Keeps the allocated memory
+    /// for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.base.clear()
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of values in the set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end` and the set contains a value within `end..=start`.
+    /// Other inverted ranges are empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::VecBTreeSet;
+    ///
+    /// let set: VecBTreeSet<_> = [3, 5, 8].into_iter().collect();
+    ///
+    /// assert_eq!(set.range(4..).next(), Some(&5));
+    /// assert_eq!(set.range(..=5).count(), 2);
+    /// ```
+    #[inline]
+    pub fn range<Q, R>(&self, range: R) -> slice::Iter<'_, T>
+    where
+        Q: Comparable<T> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let indices = range_indices(&range, self.base.len(), |v| self.equal_range(v));
+        self.base[indices].iter()
+    }
+}
+
+impl<T> Deref for VecBTreeSet<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.base
+    }
+}
+
+impl<T: Clone> Clone for VecBTreeSet<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            base: self.base.clone(),
+        }
+    }
+}
+
+impl<T: Debug> Debug for VecBTreeSet<T> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T> From<VecBTreeSet<T>> for alloc::vec::Vec<T> {
+    /// Takes over the set's vec without copying.
+    #[inline]
+    fn from(set: VecBTreeSet<T>) -> Self {
+        crate::vec::into_std(set.base)
+    }
+}
+
+impl<T: Ord> FromIterator<T> for VecBTreeSet<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut base: Vec<_> = iter.into_iter().collect();
+        base.sort();
+        base.dedup();
+        Self { base }
+    }
+}
+
+impl<T: Ord> Extend<T> for VecBTreeSet<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let mut other: Vec<_> = iter.into_iter().collect();
+        other.sort();
+        other.dedup();
+        merge_by(&mut self.base, other, |t| t, &Natural, |l, _| l);
+    }
+}
+
+impl<'a, T: Ord + Copy> Extend<&'a T> for VecBTreeSet<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        self.extend(iter.into_iter().copied());
+    }
+}
+
+impl<T> IntoIterator for VecBTreeSet<T> {
+    type Item = T;
+    type IntoIter = vec::IntoIter<T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.base.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a VecBTreeSet<T> {
+    type Item = &'a T;
+    type IntoIter = slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
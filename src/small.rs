@@ -0,0 +1,644 @@
+use crate::extend::{merge, sort_dedup};
+use crate::{
+    ArrayIntoIter, ArrayVecBTreeMap, Comparable, Comparator, IntoIter, Iter, IterMut, Keys,
+    Natural, Range, RangeMut, Values, ValuesMut, VecBTreeMap, equal_range, range_indices, vec,
+};
+use allocator_api2::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::FusedIterator;
+use core::mem;
+use core::ops::{self, Deref, Index, RangeBounds};
+
+enum Storage<K, V, const N: usize> {
+    Inline(ArrayVecBTreeMap<K, V, N, ()>),
+    Heap(Vec<(K, V)>),
+}
+
+/// A [`VecBTreeMap`] that stores up to `N` key-value pairs inline and only allocates
+/// once it grows beyond that.
+///
+/// Once the map spills to the heap, removing pairs does not move it back inline.
+/// Only converting a [`VecBTreeMap`] whose pairs fit stores them inline again.
+///
+/// # Examples
+///
+/// ```
+/// use vec_btree_map::SmallVecBTreeMap;
+///
+/// let mut map = SmallVecBTreeMap::<_, _, 2>::new();
+/// map.insert("b", 2);
+/// map.insert("a", 1);
+/// assert!(!map.spilled());
+///
+/// map.insert("c", 3);
+/// assert!(map.spilled());
+/// assert_eq!(*map, [("a", 1), ("b", 2), ("c", 3)]);
+/// ```
+pub struct SmallVecBTreeMap<K, V, const N: usize, C = Natural> {
+    base: Storage<K, V, N>,
+    cmp: C,
+}
+
+impl<K, V, const N: usize, C: Default> Default for SmallVecBTreeMap<K, V, N, C> {
+    #[inline]
+    fn default() -> Self {
+        Self::with_comparator(C::default())
+    }
+}
+
+impl<K, V, const N: usize> SmallVecBTreeMap<K, V, N> {
+    /// Constructs a new, empty `SmallVecBTreeMap<K, V, N>`.
+    ///
+    /// The map will not allocate until more than `N` pairs are inserted.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_comparator(Natural)
+    }
+
+    /// Constructs a new, empty `SmallVecBTreeMap<K, V, N>` with at least the specified capacity.
+    ///
+    /// If `capacity` is greater than `N`, the map is allocated on the heap right away.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_comparator(capacity, Natural)
+    }
+}
+
+impl<K, V, const N: usize, C> SmallVecBTreeMap<K, V, N, C> {
+    /// Constructs a new, empty `SmallVecBTreeMap<K, V, N, C>` which orders its keys with `cmp`.
+    #[inline]
+    #[must_use]
+    pub const fn with_comparator(cmp: C) -> Self {
+        Self {
+            base: Storage::Inline(ArrayVecBTreeMap::with_comparator(())),
+            cmp,
+        }
+    }
+
+    /// Constructs a new, empty `SmallVecBTreeMap<K, V, N, C>` with at least the specified
+    /// capacity, which orders its keys with `cmp`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_and_comparator(capacity: usize, cmp: C) -> Self {
+        if capacity > N {
+            Self {
+                base: Storage::Heap(Vec::with_capacity(capacity)),
+                cmp,
+            }
+        } else {
+            Self::with_comparator(cmp)
+        }
+    }
+
+    /// Returns a reference to the map's [`Comparator`].
+    #[inline]
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /// Returns `true` if the key-value pairs have been moved to the heap.
+    #[inline]
+    pub fn spilled(&self) -> bool {
+        matches!(self.base, Storage::Heap(_))
+    }
+
+    /// Returns the number of key-value pairs the map can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match &self.base {
+            Storage::Inline(_) => N,
+            Storage::Heap(base) => base.capacity(),
+        }
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self) -> &mut [(K, V)] {
+        match &mut self.base {
+            Storage::Inline(base) => base.as_mut_slice(),
+            Storage::Heap(base) => base,
+        }
+    }
+
+    /// Moves the key-value pairs to the heap if they are not there yet, reserving room
+    /// for `additional` more.
+    fn spill(&mut self, additional: usize) -> &mut Vec<(K, V)> {
+        if let Storage::Inline(inline) = &mut self.base {
+            let mut base = Vec::with_capacity(inline.len() + additional);
+            base.extend(mem::take(inline));
+            self.base = Storage::Heap(base);
+        }
+        match &mut self.base {
+            Storage::Heap(base) => base,
+            Storage::Inline(_) => unreachable!(),
+        }
+    }
+
+    /// Moves the key-value pairs back inline if they fit.
+    fn unspill(&mut self) {
+        if let Storage::Heap(base) = &mut self.base
+            && base.len() <= N
+        {
+            let mut inline = ArrayVecBTreeMap::with_comparator(());
+            for (i, e) in mem::take(base).into_iter().enumerate() {
+                // SAFETY: `i < len <= N`, and the pairs are already sorted.
+                unsafe { inline.insert_at(i, e) };
+            }
+            self.base = Storage::Inline(inline);
+        }
+    }
+
+    /// Gets an iterator over the entries of the map, sorted by key.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, V> {
+        Iter::new(self.deref().iter())
+    }
+
+    /// Gets a mutable iterator over the entries of the map, sorted by key.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
+        IterMut::new(self.as_mut_slice().iter_mut())
+    }
+
+    /// Gets an iterator over the keys of the map, in sorted order.
+    #[inline]
+    pub fn keys(&self) -> Keys<'_, K, V> {
+        Keys::new(self.deref().iter())
+    }
+
+    /// Gets an iterator over the values of the map, in order by key.
+    #[inline]
+    pub fn values(&self) -> Values<'_, K, V> {
+        Values::new(self.deref().iter())
+    }
+
+    /// Gets a mutable iterator over the values of the map, in order by key.
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, K, V> {
+        ValuesMut::new(self.as_mut_slice().iter_mut())
+    }
+
+    /// Returns the key-value pair at the given position, or [`None`] if
+    /// `index` is out of bounds.
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        self.deref().get(index).map(|e| (&e.0, &e.1))
+    }
+
+    /// Returns the key and a mutable reference to the value at the given position,
+    /// or [`None`] if `index` is out of bounds.
+    #[inline]
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        self.as_mut_slice().get_mut(index).map(|e| (&e.0, &mut e.1))
+    }
+
+    /// Removes the last key-value pair from the map and returns it, or [`None`] if it
+    /// is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        match &mut self.base {
+            Storage::Inline(base) => base.pop(),
+            Storage::Heap(base) => base.pop(),
+        }
+    }
+
+    /// Clears the map, removing all key-value pairs. Keeps the allocated memory
+    /// for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        match &mut self.base {
+            Storage::Inline(base) => base.clear(),
+            Storage::Heap(base) => base.clear(),
+        }
+    }
+
+    /// Consumes the map and returns a vec of its key-value pairs, sorted by key.
+    ///
+    /// This does not reallocate if the map has spilled to the heap.
+    #[inline]
+    #[must_use]
+    pub fn into_vec(mut self) -> alloc::vec::Vec<(K, V)> {
+        vec::into_std(mem::take(self.spill(0)))
+    }
+}
+
+impl<K, V, const N: usize, C> SmallVecBTreeMap<K, V, N, C>
+where
+    C: Comparator<K>,
+{
+    /// Binary searches this map for a given key.
+    ///
+    /// See [`VecBTreeMap::binary_search`] for details.
+    #[inline]
+    pub fn binary_search<Q>(&self, k: &Q) -> Result<usize, usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.deref()
+            .binary_search_by(|e| k.compare(&e.0, &self.cmp).reverse())
+    }
+
+    /// Returns the positions of all keys that compare equal to `k`.
+    #[inline]
+    fn equal_range<Q>(&self, k: &Q) -> ops::Range<usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        equal_range(self.deref(), |e| k.compare(&e.0, &self.cmp).reverse())
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.get_key_value(k).map(|(_, v)| v)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let i = self.binary_search(k).ok()?;
+        Some(&mut self.as_mut_slice()[i].1)
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.get_index(self.binary_search(k).ok()?)
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.binary_search(k).is_ok()
+    }
+
+    /// Inserts a key-value pair into the map, moving all pairs to the heap if the
+    /// inline storage is full.
+    ///
+    /// If the map did not have this key present, [`None`] is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned. The key is not updated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        match self.binary_search(&k) {
+            Ok(i) => Some(mem::replace(&mut self.as_mut_slice()[i].1, v)),
+            Err(i) => {
+                match &mut self.base {
+                    // SAFETY: `i <= len` comes from the binary search and `len < N` was checked.
+                    Storage::Inline(base) if !base.is_full() => unsafe {
+                        base.insert_at(i, (k, v))
+                    },
+                    _ => self.spill(N.max(1)).insert(i, (k, v)),
+                }
+                None
+            }
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.remove_entry(k).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key
+    /// was previously in the map.
+    ///
+    /// The key may be any borrowed form of the map's key type, or any other [`Comparable`]
+    /// type, but the ordering *must* match the ordering of the key type.
+    #[inline]
+    pub fn remove_entry<Q>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let i = self.binary_search(k).ok()?;
+        Some(match &mut self.base {
+            Storage::Inline(base) => base.remove_at(i),
+            Storage::Heap(base) => base.remove(i),
+        })
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of key-value pairs in the map.
+    ///
+    /// See [`VecBTreeMap::range`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end` and the map contains a key within `end..=start`.
+    /// Other inverted ranges are empty.
+    #[inline]
+    pub fn range<Q, R>(&self, range: R) -> Range<'_, K, V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let indices = range_indices(&range, self.len(), |k| self.equal_range(k));
+        Range::new(self.deref()[indices].iter())
+    }
+
+    /// Constructs a mutable double-ended iterator over a sub-range of key-value pairs in the map.
+    ///
+    /// See [`VecBTreeMap::range_mut`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end` and the map contains a key within `end..=start`.
+    /// Other inverted ranges are empty.
+    #[inline]
+    pub fn range_mut<Q, R>(&mut self, range: R) -> RangeMut<'_, K, V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let indices = range_indices(&range, self.len(), |k| self.equal_range(k));
+        RangeMut::new(self.as_mut_slice()[indices].iter_mut())
+    }
+}
+
+impl<K, V, const N: usize, C> Deref for SmallVecBTreeMap<K, V, N, C> {
+    type Target = [(K, V)];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        match &self.base {
+            Storage::Inline(base) => base,
+            Storage::Heap(base) => base,
+        }
+    }
+}
+
+impl<K, V, const N: usize, C, Q> Index<&Q> for SmallVecBTreeMap<K, V, N, C>
+where
+    C: Comparator<K>,
+    Q: Comparable<K, C> + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// Use [`get_index`](SmallVecBTreeMap::get_index) to look up a pair by its position instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    #[inline]
+    fn index(&self, k: &Q) -> &Self::Output {
+        self.get(k).expect("no entry found for key")
+    }
+}
+
+impl<K: Clone, V: Clone, const N: usize, C: Clone> Clone for SmallVecBTreeMap<K, V, N, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            base: match &self.base {
+                Storage::Inline(base) => Storage::Inline(base.clone()),
+                Storage::Heap(base) => Storage::Heap(base.clone()),
+            },
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+impl<K: PartialEq, V: PartialEq, const N: usize, C> PartialEq for SmallVecBTreeMap<K, V, N, C> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl<K: Eq, V: Eq, const N: usize, C> Eq for SmallVecBTreeMap<K, V, N, C> {}
+
+impl<K: Hash, V: Hash, const N: usize, C> Hash for SmallVecBTreeMap<K, V, N, C> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state);
+    }
+}
+
+impl<K: Debug, V: Debug, const N: usize, C> Debug for SmallVecBTreeMap<K, V, N, C> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, const N: usize, C> From<VecBTreeMap<K, V, C>> for SmallVecBTreeMap<K, V, N, C> {
+    /// Moves the key-value pairs inline if they fit, otherwise the vec is reused.
+    #[inline]
+    fn from(map: VecBTreeMap<K, V, C>) -> Self {
+        let mut small = Self::with_comparator(map.cmp);
+        small.base = Storage::Heap(map.base);
+        small.unspill();
+        small
+    }
+}
+
+impl<K, V, const N: usize, C> From<SmallVecBTreeMap<K, V, N, C>> for VecBTreeMap<K, V, C> {
+    #[inline]
+    fn from(mut map: SmallVecBTreeMap<K, V, N, C>) -> Self {
+        Self {
+            base: mem::take(map.spill(0)),
+            cmp: map.cmp,
+        }
+    }
+}
+
+impl<K, V, const N: usize, C: Comparator<K> + Default> FromIterator<(K, V)>
+    for SmallVecBTreeMap<K, V, N, C>
+{
+    /// Collects the pairs inline as long as they fit, and only allocates once they don't.
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut map = Self::with_comparator(C::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V, const N: usize, C: Comparator<K>> Extend<(K, V)> for SmallVecBTreeMap<K, V, N, C> {
+    /// Inserts the pairs inline one by one as long as they fit, which includes pairs
+    /// whose keys are already present. Once a new key doesn't fit, the map spills to
+    /// the heap, and the rest is collected, sorted and merged into it in `O(n + m)`.
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let mut iter = iter.into_iter();
+        let mut other = Vec::new();
+        while !self.spilled() {
+            let Some((k, v)) = iter.next() else {
+                return;
+            };
+            match self.binary_search(&k) {
+                Ok(i) => self.as_mut_slice()[i].1 = v,
+                Err(_) if self.len() == N => {
+                    other.push((k, v));
+                    break;
+                }
+                Err(_) => {
+                    self.insert(k, v);
+                }
+            }
+        }
+        other.extend(iter);
+        if other.is_empty() {
+            return;
+        }
+        sort_dedup(&mut other, &self.cmp);
+        self.spill(other.len());
+        if let Storage::Heap(base) = &mut self.base {
+            merge(base, other, &self.cmp);
+        }
+    }
+}
+
+impl<K, V, const N: usize, C> IntoIterator for SmallVecBTreeMap<K, V, N, C> {
+    type Item = (K, V);
+    type IntoIter = SmallIntoIter<K, V, N>;
+
+    /// Creates a consuming iterator. Pairs that are stored inline are moved out of
+    /// the map without allocating.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        SmallIntoIter {
+            base: match self.base {
+                Storage::Inline(base) => IntoIterStorage::Inline(base.into_iter()),
+                Storage::Heap(base) => IntoIterStorage::Heap(IntoIter::new(base.into_iter())),
+            },
+        }
+    }
+}
+
+impl<'a, K, V, const N: usize, C> IntoIterator for &'a SmallVecBTreeMap<K, V, N, C> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize, C> IntoIterator for &'a mut SmallVecBTreeMap<K, V, N, C> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = IterMut<'a, K, V>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+enum IntoIterStorage<K, V, const N: usize> {
+    Inline(ArrayIntoIter<K, V, N>),
+    Heap(IntoIter<K, V>),
+}
+
+/// An owning iterator over the entries of a [`SmallVecBTreeMap`], sorted by key.
+///
+/// Pairs that were stored inline stay inline, so consuming a map that never spilled
+/// does not allocate.
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct SmallIntoIter<K, V, const N: usize> {
+    base: IntoIterStorage<K, V, N>,
+}
+
+impl<K: Debug, V: Debug, const N: usize> Debug for SmallIntoIter<K, V, N> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.base {
+            IntoIterStorage::Inline(base) => base.fmt(f),
+            IntoIterStorage::Heap(base) => base.fmt(f),
+        }
+    }
+}
+
+impl<K, V, const N: usize> Iterator for SmallIntoIter<K, V, N> {
+    type Item = (K, V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match &mut self.base {
+            IntoIterStorage::Inline(base) => base.next(),
+            IntoIterStorage::Heap(base) => base.next(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, const N: usize> DoubleEndedIterator for SmallIntoIter<K, V, N> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match &mut self.base {
+            IntoIterStorage::Inline(base) => base.next_back(),
+            IntoIterStorage::Heap(base) => base.next_back(),
+        }
+    }
+}
+
+impl<K, V, const N: usize> ExactSizeIterator for SmallIntoIter<K, V, N> {
+    #[inline]
+    fn len(&self) -> usize {
+        match &self.base {
+            IntoIterStorage::Inline(base) => base.len(),
+            IntoIterStorage::Heap(base) => base.len(),
+        }
+    }
+}
+
+impl<K, V, const N: usize> FusedIterator for SmallIntoIter<K, V, N> {}
@@ -0,0 +1,1106 @@
+mod cursor;
+mod entry;
+
+use crate::extend::{merge_sorted, sort_dedup};
+use crate::{
+    Comparable, Comparator, Difference, Intersection, Natural, NotSorted, SymmetricDifference,
+    TryInsertError, TryReserveError, Union, VecBTreeMap, equal_range, lower_bound_index,
+    range_indices, upper_bound_index,
+};
+use alloc::vec::Vec;
+use allocator_api2::alloc::{Allocator, Global};
+use allocator_api2::vec::{self, Vec as AllocVec};
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::iter::Zip;
+use core::mem;
+use core::ops::{self, Bound, Index, RangeBounds};
+use core::slice;
+
+pub use cursor::{SoaCursor, SoaCursorMut};
+pub use entry::{SoaEntry, SoaOccupiedEntry, SoaVacantEntry};
+
+/// The keys and values of a map, walked side by side.
+type Pairs<'a, K, V> = (&'a [K], &'a [V]);
+
+/// A [`VecBTreeMap`] that stores its keys and values in two separate vectors.
+///
+/// Binary searches only ever touch the keys, so lookups stay cache friendly
+/// even when the values are large. In exchange, iterating over key-value pairs
+/// reads from two places in memory.
+///
+/// The map offers the same operations as [`VecBTreeMap`], except that it cannot
+/// be viewed as a slice of pairs. Use [`keys`](Self::keys) and [`values`](Self::values)
+/// to get at the two slices instead.
+pub struct SoaVecBTreeMap<K, V, C = Natural, A: Allocator = Global> {
+    keys: AllocVec<K, A>,
+    values: AllocVec<V, A>,
+    cmp: C,
+}
+
+impl<K, V, C: Default, A: Allocator + Clone + Default> Default for SoaVecBTreeMap<K, V, C, A> {
+    fn default() -> Self {
+        Self::with_comparator_in(C::default(), A::default())
+    }
+}
+
+impl<K, V> SoaVecBTreeMap<K, V> {
+    /// Constructs a new, empty `SoaVecBTreeMap<K, V>`.
+    ///
+    /// The map is initially created with a capacity of 0, so it will not allocate until it is first inserted into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #![allow(unused_mut)]
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let mut map: SoaVecBTreeMap<String, f64> = SoaVecBTreeMap::new();
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_comparator(Natural)
+    }
+
+    /// Constructs a new, empty `SoaVecBTreeMap<K, V>` with at least the specified capacity.
+    ///
+    /// See [`VecBTreeMap::with_capacity`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_comparator(capacity, Natural)
+    }
+
+    /// Constructs a new, empty `SoaVecBTreeMap<K, V>` with at least the specified capacity,
+    /// returning an error if the allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError`] if the capacity overflows or the allocator reports a failure.
+    #[inline]
+    pub fn try_with_capacity(capacity: usize) -> Result<Self, TryReserveError> {
+        Self::try_with_capacity_in(capacity, Global)
+    }
+
+    /// Constructs a `SoaVecBTreeMap<K, V>` from a vec whose keys are already strictly increasing.
+    ///
+    /// See [`VecBTreeMap::try_from_sorted_vec`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotSorted`] holding the unchanged vec and the position of the
+    /// first key that is not greater than its predecessor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let map = SoaVecBTreeMap::try_from_sorted_vec(vec![(1, "a"), (2, "b")]).unwrap();
+    /// assert_eq!(map.keys(), [1, 2]);
+    /// assert!(SoaVecBTreeMap::try_from_sorted_vec(vec![(2, "b"), (1, "a")]).is_err());
+    /// ```
+    #[inline]
+    pub fn try_from_sorted_vec(base: Vec<(K, V)>) -> Result<Self, NotSorted<K, V>>
+    where
+        K: Ord,
+    {
+        VecBTreeMap::try_from_sorted_vec(base).map(Self::from)
+    }
+}
+
+impl<K, V, A: Allocator + Clone> SoaVecBTreeMap<K, V, Natural, A> {
+    /// Constructs a new, empty `SoaVecBTreeMap<K, V, Natural, A>` which allocates in `alloc`.
+    ///
+    /// The map will not allocate until it is first inserted into.
+    #[inline]
+    #[must_use]
+    pub fn new_in(alloc: A) -> Self {
+        Self::with_comparator_in(Natural, alloc)
+    }
+
+    /// Constructs a new, empty `SoaVecBTreeMap<K, V, Natural, A>` with at least the
+    /// specified capacity, which allocates in `alloc`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self::with_capacity_and_comparator_in(capacity, Natural, alloc)
+    }
+
+    /// Constructs a new, empty `SoaVecBTreeMap<K, V, Natural, A>` with at least the
+    /// specified capacity, which allocates in `alloc`, returning an error if the
+    /// allocation fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError`] if the capacity overflows or the allocator reports a failure.
+    #[inline]
+    pub fn try_with_capacity_in(capacity: usize, alloc: A) -> Result<Self, TryReserveError> {
+        let mut map = Self::new_in(alloc);
+        map.try_reserve_exact(capacity)?;
+        Ok(map)
+    }
+
+    /// Constructs a `SoaVecBTreeMap<K, V, Natural, A>` from a vec whose keys are already
+    /// strictly increasing, splitting it into keys and values in `A`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotSorted`] holding the unchanged vec and the position of the
+    /// first key that is not greater than its predecessor.
+    #[inline]
+    pub fn try_from_sorted_vec_in(base: AllocVec<(K, V), A>) -> Result<Self, NotSorted<K, V, A>>
+    where
+        K: Ord,
+    {
+        VecBTreeMap::try_from_sorted_vec_in(base).map(Self::from)
+    }
+}
+
+impl<K, V, C> SoaVecBTreeMap<K, V, C> {
+    /// Constructs a new, empty `SoaVecBTreeMap<K, V, C>` which orders its keys with `cmp`.
+    #[inline]
+    #[must_use]
+    pub const fn with_comparator(cmp: C) -> Self {
+        Self {
+            keys: AllocVec::new(),
+            values: AllocVec::new(),
+            cmp,
+        }
+    }
+
+    /// Constructs a new, empty `SoaVecBTreeMap<K, V, C>` with at least the specified capacity,
+    /// which orders its keys with `cmp`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_and_comparator(capacity: usize, cmp: C) -> Self {
+        Self::with_capacity_and_comparator_in(capacity, cmp, Global)
+    }
+
+    /// Constructs a `SoaVecBTreeMap<K, V, C>` from a vec whose keys are already strictly
+    /// increasing according to `cmp`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NotSorted`] holding the unchanged vec and the position of the
+    /// first key that is not greater than its predecessor.
+    #[inline]
+    pub fn try_from_sorted_vec_and_comparator(
+        base: Vec<(K, V)>,
+        cmp: C,
+    ) -> Result<Self, NotSorted<K, V>>
+    where
+        C: Comparator<K>,
+    {
+        VecBTreeMap::try_from_sorted_vec_and_comparator(base, cmp).map(Self::from)
+    }
+}
+
+impl<K, V, C, A: Allocator> SoaVecBTreeMap<K, V, C, A> {
+    /// Constructs a new, empty `SoaVecBTreeMap<K, V, C, A>` which orders its keys with `cmp`
+    /// and allocates in `alloc`.
+    ///
+    /// The map will not allocate until it is first inserted into.
+    #[inline]
+    #[must_use]
+    pub fn with_comparator_in(cmp: C, alloc: A) -> Self
+    where
+        A: Clone,
+    {
+        Self {
+            keys: AllocVec::new_in(alloc.clone()),
+            values: AllocVec::new_in(alloc),
+            cmp,
+        }
+    }
+
+    /// Constructs a new, empty `SoaVecBTreeMap<K, V, C, A>` with at least the specified
+    /// capacity, which orders its keys with `cmp` and allocates in `alloc`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    #[must_use]
+    pub fn with_capacity_and_comparator_in(capacity: usize, cmp: C, alloc: A) -> Self
+    where
+        A: Clone,
+    {
+        Self {
+            keys: AllocVec::with_capacity_in(capacity, alloc.clone()),
+            values: AllocVec::with_capacity_in(capacity, alloc),
+            cmp,
+        }
+    }
+
+    /// Returns a reference to the map's [`Comparator`].
+    #[inline]
+    pub fn comparator(&self) -> &C {
+        &self.cmp
+    }
+
+    /// Returns a reference to the underlying allocator.
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        self.keys.allocator()
+    }
+
+    /// Returns the number of key-value pairs in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the map contains no key-value pairs.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// Returns the number of key-value pairs the map can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.keys.capacity().min(self.values.capacity())
+    }
+
+    /// Reserves capacity for at least `additional` more key-value pairs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn reserve(&mut self, additional: usize) {
+        self.keys.reserve(additional);
+        self.values.reserve(additional);
+    }
+
+    /// Reserves the minimum capacity for exactly `additional` more key-value pairs.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn reserve_exact(&mut self, additional: usize) {
+        self.keys.reserve_exact(additional);
+        self.values.reserve_exact(additional);
+    }
+
+    /// Inserts a key-value pair at position `i`.
+    ///
+    /// Both vectors grow before either is changed, so a failed allocation cannot
+    /// leave them with different lengths.
+    #[inline]
+    fn insert_index(&mut self, i: usize, k: K, v: V) {
+        self.reserve(1);
+        self.keys.insert(i, k);
+        self.values.insert(i, v);
+    }
+
+    /// Tries to reserve capacity for at least `additional` more key-value pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError`] if the capacity overflows or the allocator reports a failure.
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.keys.try_reserve(additional)?;
+        self.values.try_reserve(additional)
+    }
+
+    /// Tries to reserve the minimum capacity for exactly `additional` more key-value pairs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryReserveError`] if the capacity overflows or the allocator reports a failure.
+    #[inline]
+    pub fn try_reserve_exact(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.keys.try_reserve_exact(additional)?;
+        self.values.try_reserve_exact(additional)
+    }
+
+    /// Shrinks the capacity of the map as much as possible.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.keys.shrink_to_fit();
+        self.values.shrink_to_fit();
+    }
+
+    /// Shrinks the capacity of the map with a lower bound.
+    #[inline]
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.keys.shrink_to(min_capacity);
+        self.values.shrink_to(min_capacity);
+    }
+
+    /// Returns all keys as a sorted slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let map: SoaVecBTreeMap<_, _> = [("b", 2), ("a", 1)].into_iter().collect();
+    /// assert_eq!(map.keys(), ["a", "b"]);
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    /// Returns all values as a slice, in order by key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let map: SoaVecBTreeMap<_, _> = [("b", 2), ("a", 1)].into_iter().collect();
+    /// assert_eq!(map.values(), [1, 2]);
+    /// ```
+    #[inline]
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Returns all values as a mutable slice, in order by key.
+    #[inline]
+    pub fn values_mut(&mut self) -> &mut [V] {
+        &mut self.values
+    }
+
+    /// An iterator yielding all key-value pairs from start to end.
+    /// The iterator element type is `(&K, &V)`.
+    #[inline]
+    pub fn iter(&self) -> Zip<slice::Iter<'_, K>, slice::Iter<'_, V>> {
+        self.keys.iter().zip(self.values.iter())
+    }
+
+    /// An iterator yielding all key-value pairs from start to end, with mutable references to the values.
+    /// The iterator element type is `(&K, &mut V)`.
+    #[inline]
+    pub fn iter_mut(&mut self) -> Zip<slice::Iter<'_, K>, slice::IterMut<'_, V>> {
+        self.keys.iter().zip(self.values.iter_mut())
+    }
+
+    /// Returns the key-value pair at the given position, or [`None`] if
+    /// `index` is out of bounds.
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &V)> {
+        Some((self.keys.get(index)?, self.values.get(index)?))
+    }
+
+    /// Returns the key and a mutable reference to the value at the given position,
+    /// or [`None`] if `index` is out of bounds.
+    #[inline]
+    pub fn get_index_mut(&mut self, index: usize) -> Option<(&K, &mut V)> {
+        Some((self.keys.get(index)?, self.values.get_mut(index)?))
+    }
+
+    /// Returns the first key-value pair in the map, or [`None`] if it is empty.
+    #[inline]
+    pub fn first_key_value(&self) -> Option<(&K, &V)> {
+        self.get_index(0)
+    }
+
+    /// Returns the last key-value pair in the map, or [`None`] if it is empty.
+    #[inline]
+    pub fn last_key_value(&self) -> Option<(&K, &V)> {
+        self.get_index(self.len().checked_sub(1)?)
+    }
+
+    /// Removes the last key-value pair from the map and returns it, or [`None`] if it
+    /// is empty.
+    #[inline]
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        Some((self.keys.pop()?, self.values.pop()?))
+    }
+
+    /// Clears the map, removing all key-value pairs. Keeps the allocated memory
+    /// for reuse.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.keys.clear();
+        self.values.clear();
+    }
+
+    /// Splits the map into two at the given position. Returns everything from
+    /// `at` onwards, and leaves `self` with the pairs before `at`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `at > len`.
+    #[inline]
+    #[must_use]
+    pub fn split_off_index(&mut self, at: usize) -> Self
+    where
+        C: Clone,
+        A: Clone,
+    {
+        Self {
+            keys: self.keys.split_off(at),
+            values: self.values.split_off(at),
+            cmp: self.cmp.clone(),
+        }
+    }
+
+    /// Consumes the map and returns the underlying key and value vectors.
+    #[inline]
+    #[must_use]
+    pub fn into_parts(self) -> (AllocVec<K, A>, AllocVec<V, A>) {
+        (self.keys, self.values)
+    }
+
+    /// Consumes the map and returns its key-value pairs as a vec, sorted by key.
+    ///
+    /// Unlike [`VecBTreeMap::into_vec`], this has to interleave the keys and
+    /// values into a new allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let map: SoaVecBTreeMap<_, _> = [("b", 2), ("a", 1)].into_iter().collect();
+    /// assert_eq!(map.into_vec(), [("a", 1), ("b", 2)]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn into_vec(self) -> AllocVec<(K, V), A>
+    where
+        A: Clone,
+    {
+        VecBTreeMap::from(self).base
+    }
+
+    /// Creates a consuming iterator visiting all the keys, in sorted order.
+    /// The map cannot be used after calling this.
+    #[inline]
+    pub fn into_keys(self) -> vec::IntoIter<K, A> {
+        self.keys.into_iter()
+    }
+
+    /// Creates a consuming iterator visiting all the values, in order by key.
+    /// The map cannot be used after calling this.
+    #[inline]
+    pub fn into_values(self) -> vec::IntoIter<V, A> {
+        self.values.into_iter()
+    }
+
+    /// Returns the keys and values as two slices of the same length.
+    #[inline]
+    fn pairs(&self) -> Pairs<'_, K, V> {
+        (&self.keys, &self.values)
+    }
+}
+
+impl<K, V, C, A> SoaVecBTreeMap<K, V, C, A>
+where
+    C: Comparator<K>,
+    A: Allocator,
+{
+    /// Binary searches this map for a given key.
+    ///
+    /// See [`VecBTreeMap::binary_search`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let map: SoaVecBTreeMap<_, _> = [("a", 1), ("c", 2), ("d", 3)].into_iter().collect();
+    ///
+    /// assert_eq!(map.binary_search("a"), Ok(0));
+    /// assert_eq!(map.binary_search("b"), Err(1));
+    /// assert_eq!(map.binary_search("e"), Err(3));
+    /// ```
+    #[inline]
+    pub fn binary_search<Q>(&self, k: &Q) -> Result<usize, usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.keys
+            .binary_search_by(|e| k.compare(e, &self.cmp).reverse())
+    }
+
+    /// Returns the positions of all keys that compare equal to `k`.
+    #[inline]
+    fn equal_range<Q>(&self, k: &Q) -> ops::Range<usize>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        equal_range(&self.keys, |e| k.compare(e, &self.cmp).reverse())
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    #[inline]
+    pub fn get<Q>(&self, k: &Q) -> Option<&V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.binary_search(k).ok().map(|i| &self.values[i])
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    #[inline]
+    pub fn get_mut<Q>(&mut self, k: &Q) -> Option<&mut V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.binary_search(k).ok().map(|i| &mut self.values[i])
+    }
+
+    /// Returns the key-value pair corresponding to the supplied key.
+    #[inline]
+    pub fn get_key_value<Q>(&self, k: &Q) -> Option<(&K, &V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.binary_search(k).ok().and_then(|i| self.get_index(i))
+    }
+
+    /// Returns `true` if the map contains a value for the specified key.
+    #[inline]
+    pub fn contains_key<Q>(&self, k: &Q) -> bool
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.binary_search(k).is_ok()
+    }
+
+    /// Appends a key-value pair to the back of the map.
+    ///
+    /// If the map woudn't be sorted anymore by appending
+    /// the key-value pair to the back of the map, [`Some`]`(K, V)` is returned.
+    /// Otherwise [`None`] is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn push(&mut self, k: K, v: V) -> Option<(K, V)> {
+        if let Some(key) = self.keys.last()
+            && self.cmp.compare(key, &k).is_ge()
+        {
+            return Some((k, v));
+        }
+        self.keys.push(k);
+        self.values.push(v);
+        None
+    }
+
+    /// Inserts a key-value pair into the map.
+    ///
+    /// If the map did not have this key present, [`None`] is returned.
+    ///
+    /// If the map did have this key present, the value is updated, and the old
+    /// value is returned. The key is not updated.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let mut map = SoaVecBTreeMap::new();
+    ///
+    /// assert_eq!(map.insert("b", 1), None);
+    /// assert_eq!(map.insert("a", 2), None);
+    /// assert_eq!(map.insert("b", 3), Some(1));
+    /// assert_eq!(map.keys(), ["a", "b"]);
+    /// assert_eq!(map.values(), [2, 3]);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, k: K, v: V) -> Option<V> {
+        match self.binary_search(&k) {
+            Ok(i) => Some(mem::replace(&mut self.values[i], v)),
+            Err(i) => {
+                self.insert_index(i, k, v);
+                None
+            }
+        }
+    }
+
+    /// Inserts a key-value pair into the map, returning an error instead of panicking
+    /// if the map needs to grow and the allocation fails.
+    ///
+    /// See [`VecBTreeMap::try_insert`] for details.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TryInsertError`] holding the rejected key-value pair if the capacity
+    /// overflows or the allocator reports a failure. The map is left unchanged.
+    #[inline]
+    pub fn try_insert(&mut self, k: K, v: V) -> Result<Option<V>, TryInsertError<K, V>> {
+        match self.binary_search(&k) {
+            Ok(i) => Ok(Some(mem::replace(&mut self.values[i], v))),
+            Err(i) => match self.try_reserve(1) {
+                Ok(()) => {
+                    self.keys.insert(i, k);
+                    self.values.insert(i, v);
+                    Ok(None)
+                }
+                Err(e) => Err(TryInsertError::new((k, v), e)),
+            },
+        }
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let mut count = SoaVecBTreeMap::new();
+    /// for x in ["a", "b", "a", "c", "a"] {
+    ///     *count.entry(x).or_insert(0) += 1;
+    /// }
+    /// assert_eq!(count.values(), [3, 1, 1]);
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: K) -> SoaEntry<'_, K, V, C, A> {
+        match self.binary_search(&key) {
+            Ok(i) => SoaEntry::Occupied(SoaOccupiedEntry::new(self, i)),
+            Err(i) => SoaEntry::Vacant(SoaVacantEntry::new(self, key, i)),
+        }
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key
+    /// was previously in the map.
+    #[inline]
+    pub fn remove<Q>(&mut self, k: &Q) -> Option<V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        self.remove_entry(k).map(|(_, v)| v)
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key
+    /// was previously in the map.
+    #[inline]
+    pub fn remove_entry<Q>(&mut self, k: &Q) -> Option<(K, V)>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let i = self.binary_search(k).ok()?;
+        Some((self.keys.remove(i), self.values.remove(i)))
+    }
+
+    /// Merges the sorted and deduplicated pairs of `other` into `self` in `O(n + m)`.
+    ///
+    /// On equal keys, the key of `self` and the value of `other` are kept.
+    fn merge<B: Allocator>(&mut self, other: AllocVec<(K, V), B>)
+    where
+        A: Clone,
+    {
+        match (self.keys.last(), other.first()) {
+            (_, None) => return,
+            (Some(last), Some(first)) if self.cmp.compare(last, &first.0).is_ge() => {}
+            _ => {
+                self.reserve(other.len());
+                for (k, v) in other {
+                    self.keys.push(k);
+                    self.values.push(v);
+                }
+                return;
+            }
+        }
+        let len = self.len() + other.len();
+        let alloc = self.allocator().clone();
+        let mut keys = AllocVec::with_capacity_in(len, alloc.clone());
+        let mut values = AllocVec::with_capacity_in(len, alloc.clone());
+        let left = mem::replace(&mut self.keys, AllocVec::new_in(alloc.clone()))
+            .into_iter()
+            .zip(mem::replace(&mut self.values, AllocVec::new_in(alloc)));
+        merge_sorted(
+            left,
+            other,
+            |e| &e.0,
+            &self.cmp,
+            |(k, _), (_, v)| (k, v),
+            |(k, v)| {
+                keys.push(k);
+                values.push(v);
+            },
+        );
+        self.keys = keys;
+        self.values = values;
+    }
+
+    /// Moves all key-value pairs from `other` into `self`, leaving `other` empty.
+    ///
+    /// See [`VecBTreeMap::append`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let mut a: SoaVecBTreeMap<_, _> = [(1, "a"), (3, "c")].into_iter().collect();
+    /// let mut b: SoaVecBTreeMap<_, _> = [(3, "d"), (4, "e")].into_iter().collect();
+    ///
+    /// a.append(&mut b);
+    /// assert!(b.is_empty());
+    /// assert_eq!(a.values(), ["a", "d", "e"]);
+    /// ```
+    #[inline]
+    pub fn append(&mut self, other: &mut Self)
+    where
+        A: Clone,
+    {
+        let alloc = other.allocator().clone();
+        let mut pairs = AllocVec::with_capacity_in(other.len(), alloc.clone());
+        let keys = mem::replace(&mut other.keys, AllocVec::new_in(alloc.clone()));
+        let values = mem::replace(&mut other.values, AllocVec::new_in(alloc));
+        pairs.extend(keys.into_iter().zip(values));
+        self.merge(pairs);
+    }
+
+    /// Splits the map into two at the given key. Returns everything after the given key,
+    /// including the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let mut a: SoaVecBTreeMap<_, _> = [(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+    /// let b = a.split_off(&2);
+    ///
+    /// assert_eq!(a.keys(), [1]);
+    /// assert_eq!(b.keys(), [2, 3]);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn split_off<Q>(&mut self, k: &Q) -> Self
+    where
+        Q: Comparable<K, C> + ?Sized,
+        C: Clone,
+        A: Clone,
+    {
+        let at = self.equal_range(k).start;
+        self.split_off_index(at)
+    }
+
+    /// Constructs a double-ended iterator over a sub-range of key-value pairs in the map.
+    ///
+    /// See [`VecBTreeMap::range`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end` and the map contains a key within `end..=start`.
+    /// Other inverted ranges are empty.
+    #[inline]
+    pub fn range<Q, R>(&self, range: R) -> Zip<slice::Iter<'_, K>, slice::Iter<'_, V>>
+    where
+        Q: Comparable<K, C> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let indices = range_indices(&range, self.len(), |k| self.equal_range(k));
+        self.keys[indices.clone()]
+            .iter()
+            .zip(self.values[indices].iter())
+    }
+
+    /// Constructs a mutable double-ended iterator over a sub-range of key-value pairs in the map.
+    ///
+    /// See [`VecBTreeMap::range_mut`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if range `start > end` and the map contains a key within `end..=start`.
+    /// Other inverted ranges are empty.
+    #[inline]
+    pub fn range_mut<Q, R>(&mut self, range: R) -> Zip<slice::Iter<'_, K>, slice::IterMut<'_, V>>
+    where
+        Q: Comparable<K, C> + ?Sized,
+        R: RangeBounds<Q>,
+    {
+        let indices = range_indices(&range, self.len(), |k| self.equal_range(k));
+        self.keys[indices.clone()]
+            .iter()
+            .zip(self.values[indices].iter_mut())
+    }
+
+    /// Visits the key-value pairs of `self` and `other` in ascending key order.
+    ///
+    /// See [`VecBTreeMap::union`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let a: SoaVecBTreeMap<_, _> = [(1, "a"), (2, "b")].into_iter().collect();
+    /// let b: SoaVecBTreeMap<_, _> = [(2, "B"), (3, "C")].into_iter().collect();
+    ///
+    /// let union: Vec<_> = a.union(&b).collect();
+    /// assert_eq!(union, [(&1, &"a"), (&2, &"b"), (&3, &"C")]);
+    /// ```
+    #[inline]
+    pub fn union<'a>(&'a self, other: &'a Self) -> Union<'a, K, V, C, Pairs<'a, K, V>> {
+        Union::new(self.pairs(), other.pairs(), &self.cmp)
+    }
+
+    /// Visits the keys present in both `self` and `other` in ascending order,
+    /// together with the value from each map.
+    ///
+    /// See [`VecBTreeMap::intersection`] for details.
+    #[inline]
+    pub fn intersection<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> Intersection<'a, K, V, C, Pairs<'a, K, V>> {
+        Intersection::new(self.pairs(), other.pairs(), &self.cmp)
+    }
+
+    /// Visits the key-value pairs of `self` whose keys are not in `other`, in ascending key order.
+    ///
+    /// See [`VecBTreeMap::difference`] for details.
+    #[inline]
+    pub fn difference<'a>(&'a self, other: &'a Self) -> Difference<'a, K, V, C, Pairs<'a, K, V>> {
+        Difference::new(self.pairs(), other.pairs(), &self.cmp)
+    }
+
+    /// Visits the key-value pairs whose keys are in `self` or in `other` but not in both,
+    /// in ascending key order.
+    ///
+    /// See [`VecBTreeMap::symmetric_difference`] for details.
+    #[inline]
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> SymmetricDifference<'a, K, V, C, Pairs<'a, K, V>> {
+        SymmetricDifference::new(self.pairs(), other.pairs(), &self.cmp)
+    }
+
+    /// Returns a [`SoaCursor`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// See [`VecBTreeMap::lower_bound`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::ops::Bound;
+    /// use vec_btree_map::SoaVecBTreeMap;
+    ///
+    /// let map: SoaVecBTreeMap<_, _> = [(1, "a"), (2, "b"), (3, "c")].into_iter().collect();
+    /// let cursor = map.lower_bound(Bound::Excluded(&1));
+    /// assert_eq!(cursor.key_value(), Some((&2, &"b")));
+    /// ```
+    #[inline]
+    pub fn lower_bound<Q>(&self, bound: Bound<&Q>) -> SoaCursor<'_, K, V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let index = lower_bound_index(bound, |k| self.equal_range(k));
+        SoaCursor::new(&self.keys, &self.values, index)
+    }
+
+    /// Returns a [`SoaCursorMut`] pointing at the first element that is above the
+    /// given bound.
+    ///
+    /// See [`VecBTreeMap::lower_bound_mut`] for details.
+    #[inline]
+    pub fn lower_bound_mut<Q>(&mut self, bound: Bound<&Q>) -> SoaCursorMut<'_, K, V, C, A>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let index = lower_bound_index(bound, |k| self.equal_range(k));
+        SoaCursorMut::new(self, index)
+    }
+
+    /// Returns a [`SoaCursor`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// See [`VecBTreeMap::upper_bound`] for details.
+    #[inline]
+    pub fn upper_bound<Q>(&self, bound: Bound<&Q>) -> SoaCursor<'_, K, V>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let index = upper_bound_index(bound, self.len(), |k| self.equal_range(k));
+        SoaCursor::new(&self.keys, &self.values, index)
+    }
+
+    /// Returns a [`SoaCursorMut`] pointing at the last element that is below the
+    /// given bound.
+    ///
+    /// See [`VecBTreeMap::upper_bound_mut`] for details.
+    #[inline]
+    pub fn upper_bound_mut<Q>(&mut self, bound: Bound<&Q>) -> SoaCursorMut<'_, K, V, C, A>
+    where
+        Q: Comparable<K, C> + ?Sized,
+    {
+        let index = upper_bound_index(bound, self.len(), |k| self.equal_range(k));
+        SoaCursorMut::new(self, index)
+    }
+}
+
+impl<K, V, C, A, Q> Index<&Q> for SoaVecBTreeMap<K, V, C, A>
+where
+    C: Comparator<K>,
+    A: Allocator,
+    Q: Comparable<K, C> + ?Sized,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    ///
+    /// Use [`get_index`](SoaVecBTreeMap::get_index) to look up a pair by its position instead.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not present in the map.
+    #[inline]
+    fn index(&self, k: &Q) -> &Self::Output {
+        self.get(k).expect("no entry found for key")
+    }
+}
+
+impl<K: Clone, V: Clone, C: Clone, A: Allocator + Clone> Clone for SoaVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            values: self.values.clone(),
+            cmp: self.cmp.clone(),
+        }
+    }
+}
+
+impl<K: PartialEq, V: PartialEq, C, A: Allocator> PartialEq for SoaVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.keys() == other.keys() && self.values() == other.values()
+    }
+}
+
+impl<K: Eq, V: Eq, C, A: Allocator> Eq for SoaVecBTreeMap<K, V, C, A> {}
+
+impl<K: PartialOrd, V: PartialOrd, C, A: Allocator> PartialOrd for SoaVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<K: Ord, V: Ord, C, A: Allocator> Ord for SoaVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<K: Hash, V: Hash, C, A: Allocator> Hash for SoaVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for (k, v) in self.iter() {
+            (k, v).hash(state);
+        }
+    }
+}
+
+impl<K: Debug, V: Debug, C, A: Allocator> Debug for SoaVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<K, V, C, A> FromIterator<(K, V)> for SoaVecBTreeMap<K, V, C, A>
+where
+    C: Comparator<K> + Default,
+    A: Allocator + Clone + Default,
+{
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        VecBTreeMap::from_iter(iter).into()
+    }
+}
+
+impl<K, V, C: Comparator<K>, A: Allocator + Clone> Extend<(K, V)> for SoaVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        let mut other: AllocVec<_> = iter.into_iter().collect();
+        sort_dedup(&mut other, &self.cmp);
+        self.merge(other);
+    }
+}
+
+impl<K, V, C, A: Allocator + Clone> From<VecBTreeMap<K, V, C, A>> for SoaVecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn from(map: VecBTreeMap<K, V, C, A>) -> Self {
+        let VecBTreeMap { base, cmp } = map;
+        let mut soa =
+            Self::with_capacity_and_comparator_in(base.len(), cmp, base.allocator().clone());
+        for (k, v) in base {
+            soa.keys.push(k);
+            soa.values.push(v);
+        }
+        soa
+    }
+}
+
+impl<K, V, C, A: Allocator + Clone> From<SoaVecBTreeMap<K, V, C, A>> for VecBTreeMap<K, V, C, A> {
+    #[inline]
+    fn from(map: SoaVecBTreeMap<K, V, C, A>) -> Self {
+        let SoaVecBTreeMap { keys, values, cmp } = map;
+        let mut base = AllocVec::with_capacity_in(keys.len(), keys.allocator().clone());
+        base.extend(keys.into_iter().zip(values));
+        Self { base, cmp }
+    }
+}
+
+impl<K, V, C, A: Allocator> IntoIterator for SoaVecBTreeMap<K, V, C, A> {
+    type Item = (K, V);
+    type IntoIter = Zip<vec::IntoIter<K, A>, vec::IntoIter<V, A>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.keys.into_iter().zip(self.values)
+    }
+}
+
+impl<'a, K, V, C, A: Allocator> IntoIterator for &'a SoaVecBTreeMap<K, V, C, A> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = Zip<slice::Iter<'a, K>, slice::Iter<'a, V>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, K, V, C, A: Allocator> IntoIterator for &'a mut SoaVecBTreeMap<K, V, C, A> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = Zip<slice::Iter<'a, K>, slice::IterMut<'a, V>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
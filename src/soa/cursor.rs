@@ -0,0 +1,275 @@
+use super::SoaVecBTreeMap;
+use crate::cursor::{next_index, prev_index};
+use crate::{Comparator, Natural};
+use allocator_api2::alloc::{Allocator, Global};
+use core::fmt::{Debug, Formatter, Result};
+
+/// A cursor over a [`SoaVecBTreeMap`].
+///
+/// It behaves like a [`Cursor`](crate::Cursor), including the "ghost" non-element
+/// between the last and first elements of the map.
+///
+/// A `SoaCursor` is created with the [`SoaVecBTreeMap::lower_bound`] and
+/// [`SoaVecBTreeMap::upper_bound`] methods.
+pub struct SoaCursor<'a, K, V> {
+    keys: &'a [K],
+    values: &'a [V],
+    index: usize,
+}
+
+impl<K, V> Clone for SoaCursor<'_, K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.keys, self.values, self.index)
+    }
+}
+
+impl<K: Debug, V: Debug> Debug for SoaCursor<'_, K, V> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("SoaCursor").field(&self.key_value()).finish()
+    }
+}
+
+impl<'a, K, V> SoaCursor<'a, K, V> {
+    #[inline]
+    pub(super) const fn new(keys: &'a [K], values: &'a [V], index: usize) -> Self {
+        Self {
+            keys,
+            values,
+            index,
+        }
+    }
+
+    #[inline]
+    fn get(&self, i: usize) -> Option<(&'a K, &'a V)> {
+        Some((self.keys.get(i)?, self.values.get(i)?))
+    }
+
+    /// Moves the cursor to the next element of the map.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the first element of the map. If it is pointing to the last
+    /// element of the map then this will move it to the "ghost" non-element.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.index = next_index(self.index, self.keys.len());
+    }
+
+    /// Moves the cursor to the previous element of the map.
+    ///
+    /// If the cursor is pointing to the "ghost" non-element then this will move it to
+    /// the last element of the map. If it is pointing to the first
+    /// element of the map then this will move it to the "ghost" non-element.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.index = prev_index(self.index, self.keys.len());
+    }
+
+    /// Returns the position of the element that the cursor is currently pointing to,
+    /// or [`None`] if it is pointing to the "ghost" non-element.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.keys.len()).then_some(self.index)
+    }
+
+    /// Returns a reference to the key of the current element.
+    #[inline]
+    pub fn key(&self) -> Option<&'a K> {
+        self.keys.get(self.index)
+    }
+
+    /// Returns a reference to the value of the current element.
+    #[inline]
+    pub fn value(&self) -> Option<&'a V> {
+        self.values.get(self.index)
+    }
+
+    /// Returns a reference to the key and value of the current element.
+    #[inline]
+    pub fn key_value(&self) -> Option<(&'a K, &'a V)> {
+        self.get(self.index)
+    }
+
+    /// Returns a reference to the next element, or the first element of the map
+    /// if the cursor is pointing to the "ghost" non-element.
+    #[inline]
+    pub fn peek_next(&self) -> Option<(&'a K, &'a V)> {
+        self.get(next_index(self.index, self.keys.len()))
+    }
+
+    /// Returns a reference to the previous element, or the last element of the map
+    /// if the cursor is pointing to the "ghost" non-element.
+    #[inline]
+    pub fn peek_prev(&self) -> Option<(&'a K, &'a V)> {
+        self.get(prev_index(self.index, self.keys.len()))
+    }
+}
+
+/// A cursor over a [`SoaVecBTreeMap`] with editing operations.
+///
+/// It behaves like a [`CursorMut`](crate::CursorMut), including the "ghost"
+/// non-element between the last and first elements of the map.
+///
+/// A `SoaCursorMut` is created with the [`SoaVecBTreeMap::lower_bound_mut`] and
+/// [`SoaVecBTreeMap::upper_bound_mut`] methods.
+pub struct SoaCursorMut<'a, K, V, C = Natural, A: Allocator = Global> {
+    map: &'a mut SoaVecBTreeMap<K, V, C, A>,
+    index: usize,
+}
+
+impl<K: Debug, V: Debug, C, A: Allocator> Debug for SoaCursorMut<'_, K, V, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("SoaCursorMut")
+            .field(&self.key_value())
+            .finish()
+    }
+}
+
+impl<'a, K, V, C, A: Allocator> SoaCursorMut<'a, K, V, C, A> {
+    #[inline]
+    pub(super) fn new(map: &'a mut SoaVecBTreeMap<K, V, C, A>, index: usize) -> Self {
+        Self { map, index }
+    }
+
+    #[inline]
+    fn get_mut(&mut self, i: usize) -> Option<(&K, &mut V)> {
+        self.map.get_index_mut(i)
+    }
+
+    /// Moves the cursor to the next element of the map.
+    ///
+    /// See [`SoaCursor::move_next`] for details.
+    #[inline]
+    pub fn move_next(&mut self) {
+        self.index = next_index(self.index, self.map.len());
+    }
+
+    /// Moves the cursor to the previous element of the map.
+    ///
+    /// See [`SoaCursor::move_prev`] for details.
+    #[inline]
+    pub fn move_prev(&mut self) {
+        self.index = prev_index(self.index, self.map.len());
+    }
+
+    /// Returns the position of the element that the cursor is currently pointing to,
+    /// or [`None`] if it is pointing to the "ghost" non-element.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        (self.index < self.map.len()).then_some(self.index)
+    }
+
+    /// Returns a reference to the key of the current element.
+    #[inline]
+    pub fn key(&self) -> Option<&K> {
+        self.map.keys.get(self.index)
+    }
+
+    /// Returns a reference to the value of the current element.
+    #[inline]
+    pub fn value(&self) -> Option<&V> {
+        self.map.values.get(self.index)
+    }
+
+    /// Returns a mutable reference to the value of the current element.
+    #[inline]
+    pub fn value_mut(&mut self) -> Option<&mut V> {
+        self.map.values.get_mut(self.index)
+    }
+
+    /// Returns a reference to the key and value of the current element.
+    #[inline]
+    pub fn key_value(&self) -> Option<(&K, &V)> {
+        self.map.get_index(self.index)
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the current element.
+    #[inline]
+    pub fn key_value_mut(&mut self) -> Option<(&K, &mut V)> {
+        self.get_mut(self.index)
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the next element.
+    #[inline]
+    pub fn peek_next(&mut self) -> Option<(&K, &mut V)> {
+        self.get_mut(next_index(self.index, self.map.len()))
+    }
+
+    /// Returns a reference to the key and a mutable reference to the value of
+    /// the previous element.
+    #[inline]
+    pub fn peek_prev(&mut self) -> Option<(&K, &mut V)> {
+        self.get_mut(prev_index(self.index, self.map.len()))
+    }
+
+    /// Returns a read-only cursor pointing to the current element.
+    #[inline]
+    pub fn as_cursor(&self) -> SoaCursor<'_, K, V> {
+        SoaCursor::new(&self.map.keys, &self.map.values, self.index)
+    }
+
+    /// Removes the current element from the map and moves the cursor to the next one.
+    ///
+    /// If the cursor is currently pointing to the "ghost" non-element then no element
+    /// is removed and [`None`] is returned.
+    #[inline]
+    pub fn remove_current(&mut self) -> Option<(K, V)> {
+        (self.index < self.map.len()).then(|| {
+            let k = self.map.keys.remove(self.index);
+            (k, self.map.values.remove(self.index))
+        })
+    }
+}
+
+impl<K, V, C: Comparator<K>, A: Allocator> SoaCursorMut<'_, K, V, C, A> {
+    /// Inserts a new element into the map after the current one.
+    ///
+    /// See [`CursorMut::insert_after`](crate::CursorMut::insert_after) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn insert_after(&mut self, k: K, v: V) -> Option<(K, V)> {
+        let len = self.map.len();
+        let at = if self.index < len { self.index + 1 } else { 0 };
+        if !self.fits(at, &k) {
+            return Some((k, v));
+        }
+        self.map.insert_index(at, k, v);
+        if self.index == len {
+            self.index += 1;
+        }
+        None
+    }
+
+    /// Inserts a new element into the map before the current one.
+    ///
+    /// See [`CursorMut::insert_before`](crate::CursorMut::insert_before) for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn insert_before(&mut self, k: K, v: V) -> Option<(K, V)> {
+        if !self.fits(self.index, &k) {
+            return Some((k, v));
+        }
+        self.map.insert_index(self.index, k, v);
+        self.index += 1;
+        None
+    }
+
+    /// Returns `true` if `k` can be inserted at position `at` without breaking the order.
+    #[inline]
+    fn fits(&self, at: usize, k: &K) -> bool {
+        let SoaVecBTreeMap { keys, cmp, .. } = &*self.map;
+        at.checked_sub(1)
+            .is_none_or(|i| cmp.compare(&keys[i], k).is_lt())
+            && keys.get(at).is_none_or(|e| cmp.compare(k, e).is_lt())
+    }
+}
@@ -0,0 +1,217 @@
+use super::SoaVecBTreeMap;
+use crate::Natural;
+use allocator_api2::alloc::{Allocator, Global};
+use core::fmt::{Debug, Formatter, Result};
+use core::mem;
+
+/// A view into a single entry in a [`SoaVecBTreeMap`], which may either be vacant or occupied.
+///
+/// This `enum` is constructed from the [`entry`] method on [`SoaVecBTreeMap`].
+///
+/// [`entry`]: SoaVecBTreeMap::entry
+pub enum SoaEntry<'a, K, V, C = Natural, A: Allocator = Global> {
+    /// An occupied entry.
+    Occupied(SoaOccupiedEntry<'a, K, V, C, A>),
+    /// A vacant entry.
+    Vacant(SoaVacantEntry<'a, K, V, C, A>),
+}
+
+impl<K: Debug, V: Debug, C, A: Allocator> Debug for SoaEntry<'_, K, V, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Self::Occupied(e) => f.debug_tuple("SoaEntry").field(e).finish(),
+            Self::Vacant(e) => f.debug_tuple("SoaEntry").field(e).finish(),
+        }
+    }
+}
+
+impl<'a, K, V, C, A: Allocator> SoaEntry<'a, K, V, C, A> {
+    /// Ensures a value is in the entry by inserting the default if empty, and returns
+    /// a mutable reference to the value in the entry.
+    #[inline]
+    pub fn or_insert(self, default: V) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => e.insert(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the result of the default function if empty,
+    /// and returns a mutable reference to the value in the entry.
+    #[inline]
+    pub fn or_insert_with<F: FnOnce() -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => e.insert(default()),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting, if empty, the result of the default
+    /// function called with the entry's key, and returns a mutable reference to the value.
+    #[inline]
+    pub fn or_insert_with_key<F: FnOnce(&K) -> V>(self, default: F) -> &'a mut V {
+        match self {
+            Self::Occupied(e) => e.into_mut(),
+            Self::Vacant(e) => {
+                let value = default(e.key());
+                e.insert(value)
+            }
+        }
+    }
+
+    /// Returns a reference to this entry's key.
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Self::Occupied(e) => e.key(),
+            Self::Vacant(e) => e.key(),
+        }
+    }
+
+    /// Provides in-place mutable access to an occupied entry before any
+    /// potential inserts into the map.
+    #[inline]
+    #[must_use]
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Self::Occupied(e) = &mut self {
+            f(e.get_mut());
+        }
+        self
+    }
+}
+
+impl<'a, K, V: Default, C, A: Allocator> SoaEntry<'a, K, V, C, A> {
+    /// Ensures a value is in the entry by inserting the default value if empty,
+    /// and returns a mutable reference to the value in the entry.
+    #[inline]
+    pub fn or_default(self) -> &'a mut V {
+        self.or_insert_with(V::default)
+    }
+}
+
+/// A view into an occupied entry in a [`SoaVecBTreeMap`].
+/// It is part of the [`SoaEntry`] enum.
+pub struct SoaOccupiedEntry<'a, K, V, C = Natural, A: Allocator = Global> {
+    map: &'a mut SoaVecBTreeMap<K, V, C, A>,
+    index: usize,
+}
+
+impl<K: Debug, V: Debug, C, A: Allocator> Debug for SoaOccupiedEntry<'_, K, V, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("SoaOccupiedEntry")
+            .field("key", self.key())
+            .field("value", self.get())
+            .finish()
+    }
+}
+
+impl<'a, K, V, C, A: Allocator> SoaOccupiedEntry<'a, K, V, C, A> {
+    #[inline]
+    pub(super) fn new(map: &'a mut SoaVecBTreeMap<K, V, C, A>, index: usize) -> Self {
+        Self { map, index }
+    }
+
+    /// Gets a reference to the key in the entry.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.map.keys[self.index]
+    }
+
+    /// Returns the position of the entry in the map.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Gets a reference to the value in the entry.
+    #[inline]
+    pub fn get(&self) -> &V {
+        &self.map.values[self.index]
+    }
+
+    /// Gets a mutable reference to the value in the entry.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut V {
+        &mut self.map.values[self.index]
+    }
+
+    /// Converts the entry into a mutable reference to its value.
+    #[inline]
+    pub fn into_mut(self) -> &'a mut V {
+        &mut self.map.values[self.index]
+    }
+
+    /// Sets the value of the entry, and returns the entry's old value.
+    #[inline]
+    pub fn insert(&mut self, value: V) -> V {
+        mem::replace(self.get_mut(), value)
+    }
+
+    /// Takes the value of the entry out of the map, and returns it.
+    #[inline]
+    pub fn remove(self) -> V {
+        self.remove_entry().1
+    }
+
+    /// Takes the key-value pair of the entry out of the map, and returns it.
+    #[inline]
+    pub fn remove_entry(self) -> (K, V) {
+        let k = self.map.keys.remove(self.index);
+        (k, self.map.values.remove(self.index))
+    }
+}
+
+/// A view into a vacant entry in a [`SoaVecBTreeMap`].
+/// It is part of the [`SoaEntry`] enum.
+pub struct SoaVacantEntry<'a, K, V, C = Natural, A: Allocator = Global> {
+    map: &'a mut SoaVecBTreeMap<K, V, C, A>,
+    key: K,
+    index: usize,
+}
+
+impl<K: Debug, V, C, A: Allocator> Debug for SoaVacantEntry<'_, K, V, C, A> {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_tuple("SoaVacantEntry").field(self.key()).finish()
+    }
+}
+
+impl<'a, K, V, C, A: Allocator> SoaVacantEntry<'a, K, V, C, A> {
+    #[inline]
+    pub(super) fn new(map: &'a mut SoaVecBTreeMap<K, V, C, A>, key: K, index: usize) -> Self {
+        Self { map, key, index }
+    }
+
+    /// Gets a reference to the key that would be used when inserting a value
+    /// through the `SoaVacantEntry`.
+    #[inline]
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+
+    /// Returns the position the entry would be inserted at.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Takes ownership of the key.
+    #[inline]
+    pub fn into_key(self) -> K {
+        self.key
+    }
+
+    /// Sets the value of the entry with the `SoaVacantEntry`'s key,
+    /// and returns a mutable reference to it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the new capacity exceeds `isize::MAX` bytes.
+    #[inline]
+    pub fn insert(self, value: V) -> &'a mut V {
+        self.map.insert_index(self.index, self.key, value);
+        &mut self.map.values[self.index]
+    }
+}
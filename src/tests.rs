@@ -1,10 +1,14 @@
+#[cfg(feature = "alloc")]
 use crate::VecBTreeMap;
+#[cfg(feature = "alloc")]
 use core::ops::Deref;
 
 extern crate std;
 
+#[cfg(feature = "alloc")]
 use std::dbg;
 
+#[cfg(feature = "alloc")]
 #[test]
 fn stays_sorted() {
     let mut map = VecBTreeMap::with_capacity(3);
@@ -20,3 +24,926 @@ fn stays_sorted() {
     assert_eq!(map.remove("hi"), Some(10.3));
     assert_eq!(map.deref(), &[("bye", -7.0), ("mid", 5.0)]);
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn get_is_keyed() {
+    let mut map = VecBTreeMap::new();
+    map.insert(2usize, "two");
+    map.insert(0usize, "zero");
+
+    assert_eq!(map.get(&0), Some(&"zero"));
+    assert_eq!(map.get(&1), None);
+    assert_eq!(map.get_index(1), Some((&2, &"two")));
+    *map.get_mut(&2).unwrap() = "deux";
+    assert_eq!(map.get_key_value(&2), Some((&2, &"deux")));
+    assert!(map.contains_key(&0));
+    assert!(!map.contains_key(&1));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn entry_keeps_order() {
+    let mut map = VecBTreeMap::new();
+    for k in ["c", "a", "b", "a", "c", "a"] {
+        *map.entry(k).or_default() += 1;
+    }
+    assert_eq!(map.deref(), &[("a", 3), ("b", 1), ("c", 2)]);
+
+    if let crate::Entry::Occupied(o) = map.entry("b") {
+        assert_eq!(o.remove_entry(), ("b", 1));
+    }
+    assert_eq!(map.deref(), &[("a", 3), ("c", 2)]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn range_bounds() {
+    use core::ops::Bound::{Excluded, Included, Unbounded};
+
+    let mut map = VecBTreeMap::new();
+    for k in [1, 3, 5, 7, 9] {
+        map.insert(k, k * 10);
+    }
+
+    let keys = |r: crate::Range<'_, i32, i32>| r.map(|(k, _)| *k).collect::<alloc::vec::Vec<_>>();
+    assert_eq!(keys(map.range(3..7)), [3, 5]);
+    assert_eq!(keys(map.range(3..=7)), [3, 5, 7]);
+    assert_eq!(keys(map.range(2..8)), [3, 5, 7]);
+    assert_eq!(keys(map.range((Excluded(3), Unbounded))), [5, 7, 9]);
+    assert!(keys(map.range((Excluded(3), Included(3)))).is_empty());
+    assert_eq!(keys(map.range::<i32, _>(..)), [1, 3, 5, 7, 9]);
+    assert_eq!(map.range(4..4).len(), 0);
+    assert_eq!(map.range(..=9).next_back(), Some((&9, &90)));
+
+    for (_, v) in map.range_mut(5..) {
+        *v = 0;
+    }
+    assert_eq!(map.deref(), &[(1, 10), (3, 30), (5, 0), (7, 0), (9, 0)]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+#[should_panic]
+fn range_backwards() {
+    use core::ops::Bound::Included;
+
+    let map: VecBTreeMap<_, _> = [(1, ()), (2, ()), (3, ())].into_iter().collect();
+    let _ = map.range((Included(3), Included(1)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn into_iter() {
+    let mut map = VecBTreeMap::new();
+    map.insert("b", 2);
+    map.insert("a", 1);
+    map.insert("c", 3);
+
+    for (_, v) in &mut map {
+        *v *= 10;
+    }
+    let mut sum = 0;
+    for (_, v) in &map {
+        sum += v;
+    }
+    assert_eq!(sum, 60);
+
+    let mut iter = map.clone().into_iter();
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next_back(), Some(("c", 30)));
+    assert_eq!(iter.next(), Some(("a", 10)));
+    assert_eq!(iter.next(), Some(("b", 20)));
+    assert_eq!(iter.next(), None);
+
+    assert_eq!(map.clone().into_keys().next_back(), Some("c"));
+    assert_eq!(map.into_values().sum::<i32>(), 60);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn from_iter_last_wins() {
+    let map: VecBTreeMap<_, _> = [(3, "c"), (1, "a"), (3, "C"), (2, "b"), (1, "A")]
+        .into_iter()
+        .collect();
+    assert_eq!(map.deref(), &[(1, "A"), (2, "b"), (3, "C")]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn sorted_vec_round_trip() {
+    use alloc::string::ToString;
+    use alloc::vec;
+    use allocator_api2::vec::Vec as AllocVec;
+
+    let map = VecBTreeMap::try_from_sorted_vec(vec![(1, 'a'), (2, 'b'), (4, 'd')]).unwrap();
+    assert_eq!(map.get(&4), Some(&'d'));
+    assert_eq!(map.into_vec(), [(1, 'a'), (2, 'b'), (4, 'd')]);
+    assert!(VecBTreeMap::<i32, char>::try_from_sorted_vec(vec![]).is_ok());
+
+    for (input, index) in [
+        (vec![(1, 'a'), (1, 'b')], 1),
+        (vec![(1, 'a'), (3, 'c'), (2, 'b')], 2),
+        (vec![(2, 'b'), (1, 'a'), (3, 'c')], 1),
+    ] {
+        let err = VecBTreeMap::try_from_sorted_vec(input.clone()).unwrap_err();
+        assert_eq!(err.index(), index);
+        assert_eq!(
+            err.to_string(),
+            std::format!("key at index {index} is not greater than the previous key")
+        );
+        assert_eq!(err.as_vec()[..], input[..]);
+        assert_eq!(err.into_vec(), &input[..]);
+    }
+
+    let map = VecBTreeMap::from_sorted_vec_unchecked(vec![("a", 1), ("b", 2)]);
+    assert_eq!(map.deref(), &[("a", 1), ("b", 2)]);
+    assert_eq!(alloc::vec::Vec::from(map), [("a", 1), ("b", 2)]);
+
+    let base: AllocVec<_> = [(1, 'a'), (2, 'b')].into_iter().collect();
+    let map = VecBTreeMap::try_from_sorted_vec_in(base).unwrap();
+    assert_eq!(map.into_vec(), [(1, 'a'), (2, 'b')]);
+    let base: AllocVec<_> = [(2, 'b'), (1, 'a')].into_iter().collect();
+    let err = VecBTreeMap::try_from_sorted_vec_in(base).unwrap_err();
+    assert_eq!(err.index(), 1);
+    assert_eq!(err.into_vec(), [(2, 'b'), (1, 'a')]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn extend_merges() {
+    let mut map: VecBTreeMap<_, _> = [(1, 10), (3, 30), (5, 50)].into_iter().collect();
+    map.extend([(4, 40), (3, 33), (0, 0), (4, 44)]);
+    assert_eq!(map.deref(), &[(0, 0), (1, 10), (3, 33), (4, 44), (5, 50)]);
+    map.extend([(&7, &70), (&6, &60)]);
+    assert_eq!(map.deref()[5..], [(6, 60), (7, 70)]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn append_and_split_off() {
+    let mut a: VecBTreeMap<_, _> = [(1, 'a'), (3, 'c'), (5, 'e')].into_iter().collect();
+    let mut b: VecBTreeMap<_, _> = [(0, 'z'), (3, 'C'), (4, 'd')].into_iter().collect();
+    a.append(&mut b);
+    assert!(b.is_empty());
+    assert_eq!(
+        a.deref(),
+        &[(0, 'z'), (1, 'a'), (3, 'C'), (4, 'd'), (5, 'e')]
+    );
+
+    let mut tail: VecBTreeMap<_, _> = [(6, 'f'), (7, 'g')].into_iter().collect();
+    a.append(&mut tail);
+    assert_eq!(a.len(), 7);
+    a.append(&mut VecBTreeMap::new());
+    assert_eq!(a.len(), 7);
+
+    let high = a.split_off(&4);
+    assert_eq!(a.deref(), &[(0, 'z'), (1, 'a'), (3, 'C')]);
+    assert_eq!(high.deref(), &[(4, 'd'), (5, 'e'), (6, 'f'), (7, 'g')]);
+    assert_eq!(a.split_off(&2).deref(), &[(3, 'C')]);
+    assert!(a.split_off(&9).is_empty());
+    assert_eq!(a.len(), 2);
+
+    let mut high = high;
+    assert_eq!(high.split_off_index(4).len(), 0);
+    assert_eq!(high.split_off_index(0).len(), 4);
+    assert!(high.is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn set_stays_sorted() {
+    use crate::VecBTreeSet;
+
+    let mut set: VecBTreeSet<_> = [5, 1, 3, 1].into_iter().collect();
+    assert_eq!(set.deref(), &[1, 3, 5]);
+    assert!(set.insert(4));
+    assert!(!set.insert(3));
+    set.extend([6, 0, 4]);
+    assert_eq!(set.deref(), &[0, 1, 3, 4, 5, 6]);
+    assert!(set.remove(&3));
+    assert_eq!(
+        set.range(1..5).copied().collect::<alloc::vec::Vec<_>>(),
+        [1, 4]
+    );
+    assert_eq!((set.first(), set.last()), (Some(&0), Some(&6)));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn set_algebra() {
+    use alloc::vec::Vec;
+
+    let a: VecBTreeMap<_, _> = [1, 2, 4, 6, 7].into_iter().map(|k| (k, 'a')).collect();
+    let b: VecBTreeMap<_, _> = [0, 2, 3, 6, 8].into_iter().map(|k| (k, 'b')).collect();
+
+    let keys = |i: &mut dyn Iterator<Item = (&i32, &char)>| i.map(|(k, _)| *k).collect::<Vec<_>>();
+    assert_eq!(keys(&mut a.union(&b)), [0, 1, 2, 3, 4, 6, 7, 8]);
+    assert_eq!(keys(&mut a.difference(&b)), [1, 4, 7]);
+    assert_eq!(keys(&mut b.difference(&a)), [0, 3, 8]);
+    assert_eq!(keys(&mut a.symmetric_difference(&b)), [0, 1, 3, 4, 7, 8]);
+    assert_eq!(
+        a.intersection(&b).collect::<Vec<_>>(),
+        [(&2, &'a', &'b'), (&6, &'a', &'b')]
+    );
+    assert_eq!(a.union(&b).find(|(k, _)| **k == 2), Some((&2, &'a')));
+    assert_eq!(a.union(&VecBTreeMap::new()).count(), a.len());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn cursor_wraps_around_ghost() {
+    use core::ops::Bound::{Excluded, Included, Unbounded};
+
+    let mut map: VecBTreeMap<_, _> = [(1, 'a'), (3, 'c')].into_iter().collect();
+
+    let mut cursor = map.lower_bound(Excluded(&3));
+    assert_eq!(cursor.index(), None);
+    assert_eq!(cursor.peek_next(), Some((&1, &'a')));
+    assert_eq!(cursor.peek_prev(), Some((&3, &'c')));
+    cursor.move_next();
+    assert_eq!(cursor.key(), Some(&1));
+    cursor.move_prev();
+    cursor.move_prev();
+    assert_eq!(cursor.key(), Some(&3));
+    assert_eq!(map.upper_bound(Included(&0)).key(), None);
+    assert_eq!(map.upper_bound::<i32>(Unbounded).key(), Some(&3));
+
+    let mut cursor = map.upper_bound_mut(Included(&0));
+    assert_eq!(cursor.insert_after(2, 'b'), Some((2, 'b')));
+    assert_eq!(cursor.insert_after(0, 'z'), None);
+    assert_eq!(cursor.insert_before(4, 'd'), None);
+    assert_eq!(cursor.remove_current(), None);
+    cursor.move_prev();
+    cursor.move_prev();
+    assert_eq!(cursor.insert_before(2, 'b'), None);
+    assert_eq!(cursor.remove_current(), Some((3, 'c')));
+    assert_eq!(map.deref(), &[(0, 'z'), (1, 'a'), (2, 'b'), (4, 'd')]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn custom_comparator() {
+    use crate::{Natural, Reverse};
+    use core::ops::Bound::Included;
+
+    let mut map: VecBTreeMap<_, _, Reverse> = [(1, 'a'), (3, 'c'), (2, 'b')].into_iter().collect();
+    assert_eq!(map.deref(), &[(3, 'c'), (2, 'b'), (1, 'a')]);
+    assert_eq!(map.push(0, 'z'), None);
+    assert_eq!(map.push(5, 'e'), Some((5, 'e')));
+    assert_eq!(map.insert(5, 'e'), None);
+    assert_eq!(map.get(&2), Some(&'b'));
+    assert_eq!(map.range((Included(3), Included(1))).count(), 3);
+    assert_eq!(map.lower_bound(Included(&4)).key(), Some(&3));
+
+    let other = VecBTreeMap::try_from_sorted_vec_and_comparator(
+        alloc::vec![(4, 'd'), (2, 'B')],
+        Reverse(Natural),
+    )
+    .unwrap();
+    let keys: alloc::vec::Vec<_> = map.union(&other).map(|(k, _)| *k).collect();
+    assert_eq!(keys, [5, 4, 3, 2, 1, 0]);
+    map.extend(other);
+    assert_eq!(map.get(&2), Some(&'B'));
+    assert_eq!(map.split_off(&2).len(), 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn comparable_lookup() {
+    use crate::{Comparable, Natural};
+    use alloc::string::{String, ToString};
+    use core::cmp::Ordering;
+
+    struct Key<'a>(&'a str, u32);
+
+    impl Comparable<(String, u32)> for Key<'_> {
+        fn compare(&self, key: &(String, u32), _: &Natural) -> Ordering {
+            (self.0, self.1).cmp(&(key.0.as_str(), key.1))
+        }
+    }
+
+    let mut map: VecBTreeMap<_, _> = [("a", 1), ("b", 1), ("b", 2), ("c", 1)]
+        .into_iter()
+        .map(|(s, n)| ((s.to_string(), n), n))
+        .collect();
+
+    assert!(map.contains_key(&Key("b", 2)));
+    assert_eq!(map.range(Key("b", 0)..Key("c", 0)).count(), 2);
+    assert_eq!(map.remove(&Key("a", 1)), Some(1));
+    assert_eq!(map.get(&Key("a", 1)), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn prefix_range() {
+    use crate::{
+        ArrayVecBTreeMap, BufferedVecBTreeMap, ChunkedVecBTreeMap, Comparable, Natural,
+        SmallVecBTreeMap, SoaVecBTreeMap,
+    };
+    use core::cmp::Ordering;
+    use core::ops::Bound::{Excluded, Included};
+    use std::vec::Vec;
+
+    /// Matches every key with the given first component.
+    #[derive(Clone)]
+    struct Prefix(char);
+
+    impl Comparable<(char, u32)> for Prefix {
+        fn compare(&self, key: &(char, u32), _: &Natural) -> Ordering {
+            self.0.cmp(&key.0)
+        }
+    }
+
+    let pairs = |n| {
+        let middle = (0..n).map(|i| (('b', i), i));
+        [(('a', 0), 100)]
+            .into_iter()
+            .chain(middle)
+            .chain([(('c', 0), 200)])
+    };
+    let values = |iter: &mut dyn Iterator<Item = u32>| iter.collect::<Vec<_>>();
+    let (b, r) = ((0..7).collect::<Vec<_>>(), Prefix('b')..=Prefix('b'));
+
+    let map: VecBTreeMap<_, _> = pairs(7).collect();
+    assert_eq!(values(&mut map.range(r.clone()).map(|(_, v)| *v)), b);
+    assert_eq!(map.range(Prefix('b')..Prefix('c')).count(), 7);
+    assert_eq!(
+        map.range((Excluded(Prefix('a')), Excluded(Prefix('c'))))
+            .count(),
+        7
+    );
+    assert_eq!(map.lower_bound(Included(&Prefix('b'))).value(), Some(&0));
+    assert_eq!(map.lower_bound(Excluded(&Prefix('b'))).value(), Some(&200));
+    assert_eq!(map.upper_bound(Included(&Prefix('b'))).value(), Some(&6));
+    assert_eq!(map.upper_bound(Excluded(&Prefix('b'))).value(), Some(&100));
+    let mut split = map.clone();
+    assert_eq!(split.split_off(&Prefix('b')).len(), 8);
+    assert_eq!(split.len(), 1);
+
+    let soa: SoaVecBTreeMap<_, _> = pairs(7).collect();
+    assert_eq!(values(&mut soa.range(r.clone()).map(|(_, v)| *v)), b);
+    assert_eq!(soa.lower_bound(Included(&Prefix('b'))).value(), Some(&0));
+    assert_eq!(soa.upper_bound(Included(&Prefix('b'))).value(), Some(&6));
+    assert_eq!(soa.clone().split_off(&Prefix('b')).len(), 8);
+
+    let mut array = ArrayVecBTreeMap::<_, _, 9>::new();
+    pairs(7).for_each(|(k, v)| assert!(array.insert(k, v).unwrap().is_none()));
+    assert_eq!(values(&mut array.range(r.clone()).map(|(_, v)| *v)), b);
+
+    let small: SmallVecBTreeMap<_, _, 4> = pairs(7).collect();
+    assert_eq!(values(&mut small.range(r.clone()).map(|(_, v)| *v)), b);
+
+    let mut buffered: BufferedVecBTreeMap<_, _> = pairs(7).filter(|p| p.1 % 2 == 0).collect();
+    pairs(7).filter(|p| p.1 % 2 == 1).for_each(|(k, v)| {
+        buffered.insert(k, v);
+    });
+    assert_eq!(values(&mut buffered.range(r.clone()).map(|(_, v)| *v)), b);
+
+    let chunked: ChunkedVecBTreeMap<_, _> = pairs(2000).collect();
+    let b = (0..2000).collect::<Vec<_>>();
+    assert_eq!(values(&mut chunked.range(r).map(|(_, v)| *v)), b);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn soa_matches_aos() {
+    use crate::SoaVecBTreeMap;
+
+    let pairs = [(3, 'c'), (1, 'a'), (2, 'b'), (1, 'x')];
+    let mut soa: SoaVecBTreeMap<_, _> = pairs.into_iter().collect();
+    let aos: VecBTreeMap<_, _> = pairs.into_iter().collect();
+
+    assert_eq!(soa.keys(), [1, 2, 3]);
+    assert_eq!(soa.values(), ['x', 'b', 'c']);
+    assert!(soa.iter().eq(aos.iter()));
+
+    soa.extend([(0, 'z'), (2, 'B'), (5, 'e')]);
+    assert_eq!(soa.keys(), [0, 1, 2, 3, 5]);
+    assert_eq!(soa.get(&2), Some(&'B'));
+    assert_eq!(soa.range::<i32, _>(1..4).count(), 3);
+    assert_eq!(soa.remove(&0), Some('z'));
+
+    let aos = VecBTreeMap::from(soa.clone());
+    assert_eq!(SoaVecBTreeMap::from(aos), soa);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn soa_api_matches_aos() {
+    use crate::{SoaEntry, SoaVecBTreeMap};
+    use core::ops::Bound::{Excluded, Included, Unbounded};
+
+    let a_pairs = [(1, 'a'), (2, 'b'), (4, 'd'), (6, 'f')];
+    let b_pairs = [(0, 'z'), (2, 'B'), (3, 'C'), (6, 'F')];
+    let (mut soa, soa_b): (SoaVecBTreeMap<_, _>, SoaVecBTreeMap<_, _>) =
+        (a_pairs.into_iter().collect(), b_pairs.into_iter().collect());
+    let (mut aos, aos_b): (VecBTreeMap<_, _>, VecBTreeMap<_, _>) =
+        (a_pairs.into_iter().collect(), b_pairs.into_iter().collect());
+
+    assert!(soa.union(&soa_b).eq(aos.union(&aos_b)));
+    assert!(soa.intersection(&soa_b).eq(aos.intersection(&aos_b)));
+    assert!(soa.difference(&soa_b).eq(aos.difference(&aos_b)));
+    assert!(
+        soa.symmetric_difference(&soa_b)
+            .eq(aos.symmetric_difference(&aos_b))
+    );
+
+    for bound in [
+        Included(&2),
+        Excluded(&2),
+        Included(&5),
+        Excluded(&6),
+        Unbounded,
+    ] {
+        let (s, a) = (soa.lower_bound(bound), aos.lower_bound(bound));
+        assert_eq!((s.index(), s.key_value()), (a.index(), a.key_value()));
+        assert_eq!(
+            (s.peek_prev(), s.peek_next()),
+            (a.peek_prev(), a.peek_next())
+        );
+        let (s, a) = (soa.upper_bound(bound), aos.upper_bound(bound));
+        assert_eq!((s.index(), s.key_value()), (a.index(), a.key_value()));
+    }
+
+    let mut cursor = soa.upper_bound_mut(Excluded(&4));
+    assert_eq!(cursor.insert_after(3, 'c'), None);
+    assert_eq!(cursor.insert_after(5, 'e'), Some((5, 'e')));
+    cursor.move_next();
+    assert_eq!(cursor.remove_current(), Some((3, 'c')));
+    assert_eq!(cursor.key(), Some(&4));
+
+    *soa.entry(7).or_insert('g') = 'G';
+    *aos.entry(7).or_insert('g') = 'G';
+    if let SoaEntry::Occupied(e) = soa.entry(1) {
+        assert_eq!(e.remove_entry(), (1, 'a'));
+    }
+    aos.remove(&1);
+    assert!(soa.iter().eq(aos.iter()));
+
+    let mut other = soa_b.clone();
+    soa.append(&mut other);
+    aos.append(&mut aos_b.clone());
+    assert!(other.is_empty());
+    assert!(soa.iter().eq(aos.iter()));
+
+    let (soa_high, aos_high) = (soa.split_off(&3), aos.split_off(&3));
+    assert!(soa_high.iter().eq(aos_high.iter()));
+    assert_eq!(soa.clone().into_vec(), aos.clone().into_vec());
+    assert!(
+        soa_high
+            .clone()
+            .into_keys()
+            .eq(aos_high.clone().into_keys())
+    );
+    assert!(soa_high.into_values().eq(aos_high.into_values()));
+
+    let soa = SoaVecBTreeMap::try_from_sorted_vec_in(aos.clone().into_vec()).unwrap();
+    assert_eq!(VecBTreeMap::from(soa), aos);
+    assert_eq!(
+        SoaVecBTreeMap::<i32, char>::try_from_sorted_vec(alloc::vec![(2, 'b'), (1, 'a')])
+            .unwrap_err()
+            .index(),
+        1
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn frozen_lookups() {
+    for n in 0..40 {
+        let map: VecBTreeMap<_, _> = (0..n).map(|i| (i * 2, i)).collect();
+        let frozen = map.clone().freeze();
+        assert!(frozen.iter().eq(map.iter()));
+        assert!(frozen.iter().rev().eq(map.iter().rev()));
+        for k in -1..n * 2 + 1 {
+            assert_eq!(frozen.get(&k), map.get(&k));
+        }
+        assert_eq!(frozen.thaw(), map);
+    }
+}
+
+#[test]
+fn array_map_drops_pairs() {
+    use crate::ArrayVecBTreeMap;
+    use std::rc::Rc;
+
+    let rc = Rc::new(());
+    let mut map = ArrayVecBTreeMap::<_, _, 4>::new();
+    for k in [3, 1, 4, 2] {
+        assert_eq!(map.insert(k, rc.clone()).map(|_| ()), Ok(()));
+    }
+    let err = map.insert(0, rc.clone()).unwrap_err();
+    assert_eq!(err.as_inner().0, 0);
+    drop(err);
+    assert!(map.keys().copied().eq([1, 2, 3, 4]));
+    assert_eq!(Rc::strong_count(&rc), 5);
+
+    assert!(map.remove(&2).is_some());
+    assert_eq!(map.range::<i32, _>(2..).count(), 2);
+    let clone = map.clone();
+    assert_eq!(Rc::strong_count(&rc), 7);
+
+    let mut iter = clone.into_iter();
+    assert_eq!(iter.next_back().map(|e| e.0), Some(4));
+    drop(iter);
+    drop(map);
+    assert_eq!(Rc::strong_count(&rc), 1);
+}
+
+#[test]
+fn array_map_custom_comparator() {
+    use crate::{ArrayVecBTreeMap, Natural, Reverse};
+
+    let mut map = ArrayVecBTreeMap::<_, _, 3, _>::with_comparator(Reverse(Natural));
+    for (k, v) in [(1, 'a'), (3, 'c'), (2, 'b')] {
+        assert_eq!(map.insert(k, v), Ok(None));
+    }
+    assert!(map.is_full());
+    assert_eq!(*map, [(3, 'c'), (2, 'b'), (1, 'a')]);
+    assert_eq!(map.insert(2, 'x'), Ok(Some('b')));
+    assert_eq!(map.insert(0, 'z').unwrap_err().into_inner(), (0, 'z'));
+
+    // In reverse order, `..=2` covers the keys from 3 down to 2.
+    for (_, v) in map.range_mut(..=2) {
+        *v = v.to_ascii_uppercase();
+    }
+    assert!(map.values().copied().eq(['C', 'X', 'a']));
+    assert_eq!(map.get_index(1), Some((&2, &'X')));
+    assert_eq!(map.remove_entry(&3), Some((3, 'C')));
+    assert_eq!(map.binary_search(&3), Err(0));
+    assert_eq!(map.pop(), Some((1, 'a')));
+    assert!(map.into_iter().eq([(2, 'X')]));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn small_map_spills() {
+    use crate::SmallVecBTreeMap;
+
+    let mut map = SmallVecBTreeMap::<_, _, 3>::new();
+    for k in [5, 1, 3] {
+        map.insert(k, k * 10);
+    }
+    assert!(!map.spilled());
+    map.extend([(2, 20), (3, 30)]);
+    assert!(map.spilled());
+    assert!(map.keys().copied().eq([1, 2, 3, 5]));
+
+    assert_eq!(map.remove(&2), Some(20));
+    assert!(map.spilled());
+    assert_eq!(map.range::<i32, _>(2..).count(), 2);
+
+    let small: SmallVecBTreeMap<_, _, 3> = VecBTreeMap::from(map.clone()).into();
+    assert!(!small.spilled());
+    assert_eq!(small, map);
+    assert!(small.into_iter().eq(map.into_iter()));
+
+    let inline: SmallVecBTreeMap<_, _, 3> = [(2, 'b'), (1, 'a'), (2, 'c')].into_iter().collect();
+    assert!(!inline.spilled());
+    assert_eq!(*inline, [(1, 'a'), (2, 'c')]);
+    let mut iter = inline.into_iter();
+    assert_eq!(iter.len(), 2);
+    assert_eq!(iter.next_back(), Some((2, 'c')));
+    assert_eq!(iter.next(), Some((1, 'a')));
+    assert_eq!(iter.next(), None);
+
+    let heap: SmallVecBTreeMap<_, _, 3> = (0..5).rev().map(|k| (k, k)).collect();
+    assert!(heap.spilled());
+    assert!(heap.into_iter().eq((0..5).map(|k| (k, k))));
+
+    let mut full: SmallVecBTreeMap<_, _, 3> = [(1, 'a'), (2, 'b'), (3, 'c')].into_iter().collect();
+    full.extend([(3, 'x'), (1, 'y'), (3, 'z')]);
+    assert!(!full.spilled());
+    assert_eq!(*full, [(1, 'y'), (2, 'b'), (3, 'z')]);
+    full.extend([(2, 'w'), (0, 'v'), (2, 'u')]);
+    assert!(full.spilled());
+    assert_eq!(*full, [(0, 'v'), (1, 'y'), (2, 'u'), (3, 'z')]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn custom_allocator() {
+    use allocator_api2::alloc::{AllocError, Allocator, Global};
+    use core::alloc::Layout;
+    use core::cell::Cell;
+    use core::ptr::NonNull;
+
+    #[derive(Clone, Copy)]
+    struct Counting<'a>(&'a Cell<usize>);
+
+    unsafe impl Allocator for Counting<'_> {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            self.0.set(self.0.get() + 1);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    let count = Cell::new(0);
+    let mut map = VecBTreeMap::new_in(Counting(&count));
+    map.insert(2, 'b');
+    map.extend([(3, 'c'), (1, 'a')]);
+    let allocations = count.get();
+    assert!(allocations > 0);
+
+    let clone = map.clone();
+    assert!(count.get() > allocations);
+    assert!(clone.into_keys().eq([1, 2, 3]));
+    assert!(core::ptr::eq(map.split_off(&2).allocator().0, &count));
+
+    map.insert(0, 'z');
+    let frozen = map.freeze();
+    assert_eq!(frozen.get(&1), Some(&'a'));
+    let allocations = count.get();
+    let base = frozen.thaw().into_vec();
+    assert_eq!(base, [(0, 'z'), (1, 'a')]);
+    assert_eq!(count.get(), allocations);
+    assert!(core::ptr::eq(base.allocator().0, &count));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn fallible_allocation() {
+    use allocator_api2::alloc::{AllocError, Allocator, Global};
+    use core::alloc::Layout;
+    use core::cell::Cell;
+    use core::ptr::NonNull;
+
+    /// Serves allocations up to a fixed number of bytes, then fails.
+    #[derive(Clone, Copy)]
+    struct Capped<'a>(&'a Cell<usize>);
+
+    unsafe impl Allocator for Capped<'_> {
+        fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+            let left = self.0.get().checked_sub(layout.size()).ok_or(AllocError)?;
+            self.0.set(left);
+            Global.allocate(layout)
+        }
+
+        unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+            self.0.set(self.0.get() + layout.size());
+            unsafe { Global.deallocate(ptr, layout) }
+        }
+    }
+
+    let budget = Cell::new(4 * size_of::<(u32, u32)>());
+    let mut map = VecBTreeMap::try_with_capacity_in(4, Capped(&budget)).unwrap();
+    for i in 0..4 {
+        assert_eq!(map.try_insert(i, i), Ok(None));
+    }
+    assert_eq!(map.try_insert(0, 10), Ok(Some(0)));
+
+    let err = map.try_insert(4, 4).unwrap_err();
+    assert_eq!(err.into_inner(), (4, 4));
+    assert_eq!(map.len(), 4);
+    assert!(map.try_reserve_exact(1).is_err());
+
+    map.remove(&3);
+    assert_eq!(map.try_insert(3, 3), Ok(None));
+    assert!(map.keys().eq(&[0, 1, 2, 3]));
+    assert!(VecBTreeMap::<u32, u32, _, _>::try_with_capacity_in(5, Capped(&budget)).is_err());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn buffered_matches_plain() {
+    use crate::BufferedVecBTreeMap;
+
+    let mut plain = VecBTreeMap::new();
+    let mut buffered = BufferedVecBTreeMap::new();
+    let mut k = 0u32;
+    for i in 0..2000 {
+        k = (k + 7919) % 1009;
+        assert_eq!(buffered.insert(k, i), plain.insert(k, i));
+        if i % 7 == 0 {
+            let r = (k * 3) % 1009;
+            assert_eq!(buffered.remove(&r), plain.remove(&r));
+        }
+        assert_eq!(buffered.len(), plain.len());
+    }
+    assert!(buffered.pending() <= 32);
+    for k in [1500, 1501] {
+        assert_eq!(buffered.insert(k, 0), plain.insert(k, 0));
+    }
+    assert!(buffered.pending() > 0);
+    assert!(buffered.iter().eq(plain.iter()));
+    assert!(buffered.iter().rev().eq(plain.iter().rev()));
+    assert_eq!(buffered.iter().len(), plain.len());
+    for k in 0..1009 {
+        assert_eq!(buffered.get(&k), plain.get(&k));
+    }
+    for (lo, hi) in [(0, 1009), (100, 200), (500, 1600)] {
+        assert!(buffered.range(lo..hi).eq(plain.range(lo..hi)));
+        assert!(buffered.range(lo..=hi).rev().eq(plain.range(lo..=hi).rev()));
+    }
+    let (mut iter, mut expected) = (buffered.iter(), plain.iter());
+    while iter.len() > 0 {
+        assert_eq!(iter.next(), expected.next());
+        assert_eq!(iter.next_back(), expected.next_back());
+        assert_eq!(iter.len(), expected.len());
+    }
+    assert_eq!(buffered.into_map(), plain);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn chunked_matches_plain() {
+    use crate::ChunkedVecBTreeMap;
+    use alloc::vec::Vec;
+
+    let mut plain = VecBTreeMap::new();
+    let mut chunked = ChunkedVecBTreeMap::new();
+    let mut k = 0u32;
+    for i in 0..6000 {
+        k = (k + 7919) % 4001;
+        assert_eq!(chunked.insert(k, i), plain.insert(k, i));
+    }
+    for i in 0..3000u32 {
+        let r = (i * 13) % 4001;
+        assert_eq!(chunked.remove(&r), plain.remove(&r));
+    }
+    assert_eq!(chunked.len(), plain.len());
+    assert!(chunked.iter().eq(plain.iter()));
+    assert!(chunked.iter().rev().eq(plain.iter().rev()));
+    for i in [0, 1, 511, 512, 1000, plain.len() - 1, plain.len()] {
+        assert_eq!(chunked.get_index(i), plain.get_index(i));
+    }
+    for (lo, hi) in [(0, 4001), (100, 900), (1000, 1001), (2500, 2400)] {
+        let a = chunked.range(lo..hi.max(lo));
+        assert_eq!(a.len(), plain.range(lo..hi.max(lo)).len());
+        assert!(a.eq(plain.range(lo..hi.max(lo))));
+    }
+    let rebuilt = ChunkedVecBTreeMap::from(plain.clone());
+    assert_eq!(rebuilt, chunked);
+
+    let batch: Vec<_> = (0..3000u32).map(|i| ((i * 7) % 5000 / 2, i)).collect();
+    chunked.extend(batch.iter().copied());
+    plain.extend(batch);
+    assert_eq!(chunked.len(), plain.len());
+    assert!(chunked.iter().eq(plain.iter()));
+    for k in (0..2600).step_by(3) {
+        assert_eq!(chunked.remove(&k), plain.remove(&k));
+        assert_eq!(chunked.insert(k + 1, 0), plain.insert(k + 1, 0));
+    }
+    for k in 0..4001 {
+        assert_eq!(chunked.get(&k), plain.get(&k));
+    }
+    let mut empty = ChunkedVecBTreeMap::new();
+    empty.extend(plain.iter().map(|(&k, &v)| (k, v)));
+    assert_eq!(empty, chunked);
+    assert_eq!(chunked.into_map(), plain);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_matches_sequential() {
+    use rayon::prelude::*;
+    use std::vec::Vec;
+
+    let pairs: Vec<_> = (0..10_000u32).map(|i| ((i * 7919) % 3001, i)).collect();
+    let seq: VecBTreeMap<_, _> = pairs.iter().copied().collect();
+    let par: VecBTreeMap<_, _> = pairs.par_iter().copied().collect();
+    assert_eq!(par, seq);
+
+    let mut extended: VecBTreeMap<_, _> = (0..100).map(|i| (i * 40, 0)).collect();
+    let mut expected = extended.clone();
+    extended.par_extend(pairs.par_iter().copied());
+    expected.extend(pairs.iter().copied());
+    assert_eq!(extended, expected);
+
+    assert!(
+        par.par_keys()
+            .copied()
+            .collect::<Vec<_>>()
+            .into_iter()
+            .eq(seq.keys().copied())
+    );
+    assert_eq!(par.par_iter().len(), seq.len());
+    let (mut doubled, owned) = (par.clone(), par.clone());
+    doubled.par_values_mut().for_each(|v| *v *= 2);
+    (&mut doubled).into_par_iter().for_each(|(_, v)| *v /= 2);
+    assert_eq!(doubled, seq);
+    assert_eq!(owned.into_par_iter().collect::<Vec<_>>(), Vec::from(seq));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_unsorted() {
+    use serde::Deserialize;
+    use serde::de::value::{Error, MapDeserializer};
+
+    let input = || (0..10_000u32).rev().map(|i| (i % 5000, i));
+    let expected: VecBTreeMap<_, _> = input().collect();
+    let map = VecBTreeMap::deserialize(MapDeserializer::<_, Error>::new(input())).unwrap();
+    assert_eq!(map, expected);
+    assert_eq!(map.get(&4999), Some(&4999));
+
+    let map: VecBTreeMap<u32, u32> =
+        crate::serde::first_wins::deserialize(MapDeserializer::<_, Error>::new(input())).unwrap();
+    assert_eq!(map.len(), 5000);
+    assert_eq!(map.get(&4999), Some(&9999));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_policies() {
+    use crate::serde::{first_wins, last_wins, strict};
+    use alloc::string::ToString;
+    use serde::de::value::{Error, MapDeserializer};
+
+    type Pairs<'a> = core::iter::Copied<core::slice::Iter<'a, (u32, char)>>;
+    fn de(pairs: &[(u32, char)]) -> MapDeserializer<'_, Pairs<'_>, Error> {
+        MapDeserializer::new(pairs.iter().copied())
+    }
+
+    let sorted = [(1, 'a'), (2, 'b'), (3, 'c')];
+    let duplicate = [(1, 'a'), (2, 'b'), (1, 'x'), (3, 'c')];
+
+    let strict =
+        |pairs| -> Result<VecBTreeMap<u32, char>, Error> { strict::deserialize(de(pairs)) };
+    assert_eq!(strict(&sorted).unwrap().deref(), &sorted);
+    assert_eq!(
+        strict(&duplicate).unwrap_err().to_string(),
+        "duplicate key at position 2"
+    );
+    assert_eq!(
+        strict(&[(2, 'b'), (1, 'a')]).unwrap_err().to_string(),
+        "key at position 1 is not greater than the previous key"
+    );
+
+    let map: VecBTreeMap<u32, char> = last_wins::deserialize(de(&duplicate)).unwrap();
+    assert_eq!(map.deref(), &[(1, 'x'), (2, 'b'), (3, 'c')]);
+    let map: VecBTreeMap<u32, char> = first_wins::deserialize(de(&duplicate)).unwrap();
+    assert_eq!(map.deref(), &[(1, 'a'), (2, 'b'), (3, 'c')]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_in_place() {
+    use serde::Deserialize;
+    use serde::de::value::{Error, MapDeserializer};
+
+    let mut map: VecBTreeMap<u32, u32> = (0..100).map(|i| (i, i)).collect();
+    let (ptr, capacity) = (map.as_ptr(), map.capacity());
+
+    let input = (0..50u32).rev().map(|i| (i % 25, i));
+    VecBTreeMap::deserialize_in_place(MapDeserializer::<_, Error>::new(input), &mut map).unwrap();
+    assert_eq!(map.len(), 25);
+    assert_eq!(map.get(&0), Some(&0));
+    assert_eq!(map.get(&24), Some(&24));
+    assert_eq!((map.as_ptr(), map.capacity()), (ptr, capacity));
+
+    let input = [(1u32, "a")].into_iter();
+    assert!(
+        VecBTreeMap::deserialize_in_place(MapDeserializer::<_, Error>::new(input), &mut map)
+            .is_err()
+    );
+    assert!(map.is_empty());
+    assert_eq!(map.capacity(), capacity);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_set_unsorted() {
+    use crate::VecBTreeSet;
+    use serde::Deserialize;
+    use serde::de::value::{Error, SeqDeserializer};
+
+    let input = (0..10_000u32).rev().map(|i| i % 5000);
+    let set = VecBTreeSet::<u32>::deserialize(SeqDeserializer::<_, Error>::new(input)).unwrap();
+    assert!(set.iter().copied().eq(0..5000));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn deserialize_either_form() {
+    use crate::serde::{SeqVecBTreeMap, as_seq};
+    use serde::Deserialize;
+
+    let expected: VecBTreeMap<u32, char> = [(1, 'a'), (2, 'b')].into_iter().collect();
+    let as_map = serde_json::to_string(&expected).unwrap();
+    let as_seq = serde_json::to_string(&SeqVecBTreeMap(expected.clone())).unwrap();
+    assert_eq!(as_map, r#"{"1":"a","2":"b"}"#);
+    assert_eq!(as_seq, r#"[[1,"a"],[2,"b"]]"#);
+
+    for json in [&as_map, &as_seq] {
+        let map: VecBTreeMap<u32, char> = serde_json::from_str(json).unwrap();
+        assert_eq!(map, expected);
+
+        let mut de = serde_json::Deserializer::from_str(json);
+        let map: VecBTreeMap<u32, char> = as_seq::deserialize(&mut de).unwrap();
+        assert_eq!(map, expected);
+
+        let map: SeqVecBTreeMap<u32, char> = serde_json::from_str(json).unwrap();
+        assert_eq!(map.0, expected);
+
+        let mut map = VecBTreeMap::new();
+        let mut de = serde_json::Deserializer::from_str(json);
+        VecBTreeMap::deserialize_in_place(&mut de, &mut map).unwrap();
+        assert_eq!(map, expected);
+    }
+}
@@ -0,0 +1,21 @@
+//! Conversions between [`alloc::vec::Vec`] and the allocator-aware vec used as storage.
+
+use allocator_api2::vec::Vec;
+use core::mem::ManuallyDrop;
+
+/// Takes over a [`alloc::vec::Vec`] without copying.
+#[inline]
+pub(crate) fn from_std<T>(vec: alloc::vec::Vec<T>) -> Vec<T> {
+    let mut vec = ManuallyDrop::new(vec);
+    // SAFETY: both vecs use the same layout and both `Global`s forward to the
+    // global allocator, so the buffer can be handed over as is.
+    unsafe { Vec::from_raw_parts(vec.as_mut_ptr(), vec.len(), vec.capacity()) }
+}
+
+/// Hands the buffer of a vec back as a [`alloc::vec::Vec`] without copying.
+#[inline]
+pub(crate) fn into_std<T>(vec: Vec<T>) -> alloc::vec::Vec<T> {
+    let mut vec = ManuallyDrop::new(vec);
+    // SAFETY: see `from_std`.
+    unsafe { alloc::vec::Vec::from_raw_parts(vec.as_mut_ptr(), vec.len(), vec.capacity()) }
+}